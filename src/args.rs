@@ -0,0 +1,692 @@
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::tree::{Node, names, takes_value, value_range};
+use crate::{OpenCliArgument, OpenCliCommand, OpenCliDocument, OpenCliExitCode, OpenCliOption};
+
+/// The result of matching a command line against an [`OpenCliDocument`].
+///
+/// Each level of the tree corresponds to a command, starting with the root.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ArgMatches {
+    /// The name of the matched command, the program title for the root
+    pub name: String,
+
+    /// The options given at this command level
+    pub options: Vec<OptionMatch>,
+
+    /// The arguments given at this command level
+    pub arguments: Vec<ArgumentMatch>,
+
+    /// The matched sub command
+    pub subcommand: Option<Box<ArgMatches>>,
+}
+
+/// An option found on the command line.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OptionMatch {
+    /// The option name as defined in the document, even if an alias was used
+    pub name: String,
+
+    /// The number of times the option was given
+    pub occurrences: usize,
+
+    /// The values of all occurrences, in command line order
+    pub values: Vec<String>,
+}
+
+/// Values assigned to an argument.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ArgumentMatch {
+    /// The argument name
+    pub name: String,
+
+    /// The argument values, in command line order
+    pub values: Vec<String>,
+}
+
+/// A command line which does not match an [`OpenCliDocument`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArgsError {
+    /// The index of the offending item in the command line, counting the program name as 0
+    ///
+    /// Errors about something missing point one past the last item.
+    pub index: usize,
+
+    /// The kind of mismatch
+    pub kind: ArgsErrorKind,
+
+    /// The exit code from the document which best fits a usage error, if any
+    pub exit_code: Option<Box<OpenCliExitCode>>,
+}
+
+/// The kinds of mismatches reported by [`OpenCliDocument::parse_args`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ArgsErrorKind {
+    /// An option which is not defined for the command, with similar names as suggestions
+    #[error("Unknown option `{name}`{}", did_you_mean(.suggestions))]
+    UnknownOption {
+        name: String,
+        suggestions: Vec<String>,
+    },
+    /// An option given without a value for one of its required arguments
+    #[error("Option `{option}` requires a value for `{argument}`")]
+    MissingValue { option: String, argument: String },
+    /// A value attached to an option which takes none, e.g. `--verbose=yes`
+    #[error("Option `{0}` does not take a value")]
+    UnexpectedValue(String),
+    /// A required option which was not given
+    #[error("Missing required option `{0}`")]
+    MissingOption(String),
+    /// A required positional argument which was not given
+    #[error("Missing required argument `{0}`")]
+    MissingArgument(String),
+    /// An argument given fewer values than its arity requires
+    #[error("`{name}` expects {} values, got {count}", expected(*.minimum, *.maximum))]
+    Arity {
+        name: String,
+        minimum: usize,
+        maximum: Option<usize>,
+        count: usize,
+    },
+    /// A value which is not one of the accepted values of its argument
+    #[error("Invalid value `{value}` for `{name}`, possible values: {}", .accepted_values.join(", "))]
+    InvalidValue {
+        name: String,
+        value: String,
+        accepted_values: Vec<String>,
+    },
+    /// A positional value left over after all arguments are filled
+    #[error("Unexpected argument `{0}`")]
+    UnexpectedArgument(String),
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "argv[{}]: {}", self.index, self.kind)
+    }
+}
+
+impl std::error::Error for ArgsError {}
+
+fn did_you_mean(suggestions: &[String]) -> String {
+    match suggestions {
+        [] => String::new(),
+        [suggestion] => format!(", did you mean `{suggestion}`?"),
+        _ => format!(", did you mean one of `{}`?", suggestions.join("`, `")),
+    }
+}
+
+fn expected(minimum: usize, maximum: Option<usize>) -> String {
+    match maximum {
+        Some(maximum) if maximum == minimum => minimum.to_string(),
+        Some(maximum) => format!("{minimum} to {maximum}"),
+        None => format!("at least {minimum}"),
+    }
+}
+
+impl ArgMatches {
+    /// The names of the matched sub commands, excluding the root.
+    pub fn command_path(&self) -> Vec<&str> {
+        let mut path = Vec::new();
+        let mut matches = self;
+        while let Some(subcommand) = &matches.subcommand {
+            path.push(subcommand.name.as_str());
+            matches = subcommand;
+        }
+        path
+    }
+
+    /// The matched sub command, if any.
+    pub fn subcommand(&self) -> Option<&ArgMatches> {
+        self.subcommand.as_deref()
+    }
+
+    /// The deepest matched command.
+    pub fn leaf(&self) -> &ArgMatches {
+        let mut matches = self;
+        while let Some(subcommand) = &matches.subcommand {
+            matches = subcommand;
+        }
+        matches
+    }
+
+    /// Find an option given at this command level by its name.
+    pub fn option(&self, name: &str) -> Option<&OptionMatch> {
+        self.options.iter().find(|option| option.name == name)
+    }
+
+    /// Find an argument given at this command level by its name.
+    pub fn argument(&self, name: &str) -> Option<&ArgumentMatch> {
+        self.arguments.iter().find(|argument| argument.name == name)
+    }
+
+    /// Whether an option or argument with the given name was given at this command level.
+    pub fn is_present(&self, name: &str) -> bool {
+        self.option(name).is_some() || self.argument(name).is_some()
+    }
+
+    /// The values of an option or argument given at this command level.
+    pub fn values_of(&self, name: &str) -> Option<&[String]> {
+        self.option(name)
+            .map(|option| option.values.as_slice())
+            .or_else(|| {
+                self.argument(name)
+                    .map(|argument| argument.values.as_slice())
+            })
+    }
+
+    /// The first value of an option or argument given at this command level.
+    pub fn value_of(&self, name: &str) -> Option<&str> {
+        self.values_of(name)?.first().map(String::as_str)
+    }
+}
+
+impl OpenCliDocument {
+    /// Match a command line against the document.
+    ///
+    /// The first item is the program name and is skipped, so the result of
+    /// [`std::env::args`] can be passed directly. On a mismatch, the error points at the
+    /// offending item and carries the exit code the program should terminate with, if the
+    /// document defines a fitting one.
+    pub fn parse_args<I, T>(&self, args: I) -> Result<ArgMatches, ArgsError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let tokens = args.into_iter().skip(1).map(Into::into).collect();
+        Parser::new(self, tokens).parse()
+    }
+}
+
+/// The state of a command level while parsing.
+struct Level<'a, 'n> {
+    node: &'n Node<'a>,
+    options: Vec<OptionMatch>,
+    /// The positional values together with their index
+    positionals: Vec<(usize, String)>,
+}
+
+struct Parser<'a> {
+    document: &'a OpenCliDocument,
+    group_options: bool,
+    separator: Option<&'a str>,
+    tokens: Vec<String>,
+    position: usize,
+    /// The commands matched so far, used to look up exit codes
+    commands: Vec<&'a OpenCliCommand>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(document: &'a OpenCliDocument, tokens: Vec<String>) -> Self {
+        let conventions = document.conventions.as_ref();
+        Parser {
+            document,
+            group_options: conventions
+                .and_then(|conventions| conventions.group_options)
+                .unwrap_or(true),
+            separator: conventions
+                .and_then(|conventions| conventions.option_argument_separator.as_deref()),
+            tokens,
+            position: 0,
+            commands: Vec::new(),
+        }
+    }
+
+    fn parse(mut self) -> Result<ArgMatches, ArgsError> {
+        let nodes = Node::collect(self.document, true);
+        let mut levels = vec![Level {
+            node: &nodes[0],
+            options: Vec::new(),
+            positionals: Vec::new(),
+        }];
+
+        let mut only_positionals = false;
+        while let Some((index, token)) = self.next() {
+            let level = levels.last_mut().unwrap();
+            if only_positionals {
+                level.positionals.push((index, token));
+            } else if token == "--" {
+                only_positionals = true;
+            } else if token.starts_with("--") && token.len() > 2 {
+                self.long(level, index, &token)?;
+            } else if token.starts_with('-') && token.len() > 1 && !is_number(&token) {
+                self.short(level, index, &token)?;
+            } else if let Some(node) = subcommand(&nodes, level, &token) {
+                self.commands.extend(node.command);
+                levels.push(Level {
+                    node,
+                    options: Vec::new(),
+                    positionals: Vec::new(),
+                });
+            } else {
+                level.positionals.push((index, token));
+            }
+        }
+
+        self.check_required_options(&levels)?;
+
+        let mut matches: Option<ArgMatches> = None;
+        for level in levels.into_iter().rev() {
+            let name = match level.node.command {
+                Some(command) => command.name.clone(),
+                None => self.document.info.title.clone(),
+            };
+            matches = Some(ArgMatches {
+                name,
+                arguments: self.assign_arguments(&level.node.arguments, level.positionals)?,
+                options: level.options,
+                subcommand: matches.map(Box::new),
+            });
+        }
+        Ok(matches.unwrap())
+    }
+
+    /// The next item and its index in the command line.
+    fn next(&mut self) -> Option<(usize, String)> {
+        let token = self.tokens.get(self.position)?.clone();
+        self.position += 1;
+        Some((self.position, token))
+    }
+
+    /// The index one past the last item of the command line.
+    fn end(&self) -> usize {
+        self.tokens.len() + 1
+    }
+
+    fn error(&self, index: usize, kind: ArgsErrorKind) -> ArgsError {
+        ArgsError {
+            index,
+            kind,
+            exit_code: self.exit_code().cloned().map(Box::new),
+        }
+    }
+
+    /// The exit code for usage errors, looked up from the innermost matched command outwards.
+    ///
+    /// An exit code fits if its description mentions usage, arguments or options, or if it is
+    /// one of the conventional usage error codes 2 and 64.
+    fn exit_code(&self) -> Option<&'a OpenCliExitCode> {
+        const KEYWORDS: [&str; 4] = ["usage", "argument", "option", "syntax"];
+        const CODES: [i32; 2] = [2, 64];
+
+        let scopes = self
+            .commands
+            .iter()
+            .rev()
+            .map(|command| &command.exit_codes)
+            .chain(std::iter::once(&self.document.exit_codes));
+        for exit_codes in scopes {
+            let described = exit_codes.iter().find(|exit_code| {
+                exit_code.description.as_ref().is_some_and(|description| {
+                    let description = description.to_lowercase();
+                    KEYWORDS.iter().any(|keyword| description.contains(keyword))
+                })
+            });
+            let fitting = described.or_else(|| {
+                exit_codes
+                    .iter()
+                    .find(|exit_code| CODES.contains(&exit_code.code))
+            });
+            if fitting.is_some() {
+                return fitting;
+            }
+        }
+        None
+    }
+
+    fn unknown_option(&self, level: &Level, index: usize, name: &str) -> ArgsError {
+        self.error(
+            index,
+            ArgsErrorKind::UnknownOption {
+                name: name.to_owned(),
+                suggestions: suggestions(level, name),
+            },
+        )
+    }
+
+    fn long(
+        &mut self,
+        level: &mut Level<'a, '_>,
+        index: usize,
+        token: &str,
+    ) -> Result<(), ArgsError> {
+        let (option, inline) = self
+            .split_inline(level, token)
+            .ok_or_else(|| self.unknown_option(level, index, token))?;
+        self.consume(level, index, option, inline)
+    }
+
+    fn short(
+        &mut self,
+        level: &mut Level<'a, '_>,
+        index: usize,
+        token: &str,
+    ) -> Result<(), ArgsError> {
+        if let Some((option, inline)) = self.split_inline(level, token) {
+            return self.consume(level, index, option, inline);
+        }
+        if !self.group_options {
+            return Err(self.unknown_option(level, index, token));
+        }
+
+        // Grouped short options, e.g. `-abc` or `-ofile`
+        let flags = &token[1..];
+        for (i, flag) in flags.char_indices() {
+            let name = format!("-{flag}");
+            let option = find_option(level, &name)
+                .ok_or_else(|| self.unknown_option(level, index, &name))?;
+            let rest = &flags[i + flag.len_utf8()..];
+            if takes_value(option) && !rest.is_empty() {
+                let rest = rest
+                    .strip_prefix(self.separator.unwrap_or("="))
+                    .unwrap_or(rest);
+                return self.consume(level, index, option, Some(rest.to_owned()));
+            }
+            self.consume(level, index, option, None)?;
+        }
+        Ok(())
+    }
+
+    /// Find the option named by the token, splitting off an attached value like `--out=file`.
+    fn split_inline(
+        &self,
+        level: &Level<'a, '_>,
+        token: &str,
+    ) -> Option<(&'a OpenCliOption, Option<String>)> {
+        if let Some(option) = find_option(level, token) {
+            return Some((option, None));
+        }
+
+        // Values can be attached unless the separator is explicitly a space
+        let separator = match self.separator {
+            Some(separator) if separator.trim().is_empty() => return None,
+            Some(separator) => separator,
+            None => "=",
+        };
+        let (name, value) = token.split_once(separator)?;
+        let option = find_option(level, name)?;
+        Some((option, Some(value.to_owned())))
+    }
+
+    fn consume(
+        &mut self,
+        level: &mut Level<'a, '_>,
+        index: usize,
+        option: &'a OpenCliOption,
+        mut inline: Option<String>,
+    ) -> Result<(), ArgsError> {
+        let mut values = Vec::new();
+        for argument in &option.arguments {
+            let (minimum, maximum) = value_range(argument);
+            let mut taken = 0;
+            while maximum.is_none_or(|maximum| taken < maximum) {
+                let (value_index, value) = match inline.take() {
+                    Some(value) => (index, value),
+                    None => {
+                        let Some(next) = self.tokens.get(self.position) else {
+                            break;
+                        };
+                        // Optional values end at the next option, required ones only at a known option
+                        let stop = if taken < minimum {
+                            next == "--" || find_option(level, next).is_some()
+                        } else {
+                            looks_like_option(next)
+                        };
+                        if stop {
+                            break;
+                        }
+                        self.next().unwrap()
+                    }
+                };
+                self.check_value(argument, value_index, &value)?;
+                values.push(value);
+                taken += 1;
+            }
+            if taken == 0 && minimum > 0 {
+                return Err(self.error(
+                    self.position + 1,
+                    ArgsErrorKind::MissingValue {
+                        option: option.name.clone(),
+                        argument: argument.name.clone(),
+                    },
+                ));
+            }
+            if taken < minimum {
+                return Err(self.error(
+                    self.position + 1,
+                    ArgsErrorKind::Arity {
+                        name: argument.name.clone(),
+                        minimum,
+                        maximum,
+                        count: taken,
+                    },
+                ));
+            }
+        }
+        if inline.is_some() {
+            return Err(self.error(index, ArgsErrorKind::UnexpectedValue(option.name.clone())));
+        }
+
+        match level
+            .options
+            .iter_mut()
+            .find(|found| found.name == option.name)
+        {
+            Some(found) => {
+                found.occurrences += 1;
+                found.values.extend(values);
+            }
+            None => level.options.push(OptionMatch {
+                name: option.name.clone(),
+                occurrences: 1,
+                values,
+            }),
+        }
+        Ok(())
+    }
+
+    fn check_value(
+        &self,
+        argument: &OpenCliArgument,
+        index: usize,
+        value: &str,
+    ) -> Result<(), ArgsError> {
+        if argument.accepted_values.is_empty()
+            || argument
+                .accepted_values
+                .iter()
+                .any(|accepted| accepted == value)
+        {
+            return Ok(());
+        }
+        Err(self.error(
+            index,
+            ArgsErrorKind::InvalidValue {
+                name: argument.name.clone(),
+                value: value.to_owned(),
+                accepted_values: argument.accepted_values.clone(),
+            },
+        ))
+    }
+
+    /// Check that every required option is given at its command level, or below for recursive options.
+    fn check_required_options(&self, levels: &[Level]) -> Result<(), ArgsError> {
+        for (i, level) in levels.iter().enumerate() {
+            let options = match level.node.command {
+                Some(command) => &command.options,
+                None => &self.document.options,
+            };
+            for option in options
+                .iter()
+                .filter(|option| option.required.unwrap_or(false))
+            {
+                let scope = if option.recursive.unwrap_or(false) {
+                    &levels[i..]
+                } else {
+                    &levels[i..=i]
+                };
+                if !scope
+                    .iter()
+                    .any(|level| level.options.iter().any(|found| found.name == option.name))
+                {
+                    return Err(self.error(
+                        self.end(),
+                        ArgsErrorKind::MissingOption(option.name.clone()),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Distribute positional values over the arguments according to their arity.
+    fn assign_arguments(
+        &self,
+        arguments: &[&OpenCliArgument],
+        positionals: Vec<(usize, String)>,
+    ) -> Result<Vec<ArgumentMatch>, ArgsError> {
+        let ranges: Vec<(usize, Option<usize>)> = arguments
+            .iter()
+            .map(|argument| {
+                let (minimum, maximum) = value_range(argument);
+                if argument.required.unwrap_or(false) {
+                    (minimum.max(1), maximum)
+                } else {
+                    (0, maximum)
+                }
+            })
+            .collect();
+
+        let mut positionals = positionals.into_iter();
+        let mut remaining = positionals.len();
+        let mut matches = Vec::new();
+        for (i, argument) in arguments.iter().enumerate() {
+            let (minimum, maximum) = ranges[i];
+            let reserved: usize = ranges[i + 1..].iter().map(|(minimum, _)| minimum).sum();
+            // Fill the minimum first, so a shortage is reported for the last argument
+            let available = remaining
+                .saturating_sub(reserved)
+                .max(minimum.min(remaining));
+            let count = maximum.map_or(available, |maximum| available.min(maximum));
+            if count == 0 && minimum > 0 {
+                return Err(self.error(
+                    self.end(),
+                    ArgsErrorKind::MissingArgument(argument.name.clone()),
+                ));
+            }
+            if count < minimum {
+                return Err(self.error(
+                    self.end(),
+                    ArgsErrorKind::Arity {
+                        name: argument.name.clone(),
+                        minimum,
+                        maximum,
+                        count,
+                    },
+                ));
+            }
+            if count > 0 {
+                let mut values = Vec::with_capacity(count);
+                for (index, value) in positionals.by_ref().take(count) {
+                    self.check_value(argument, index, &value)?;
+                    values.push(value);
+                }
+                matches.push(ArgumentMatch {
+                    name: argument.name.clone(),
+                    values,
+                });
+            }
+            remaining -= count;
+        }
+
+        match positionals.next() {
+            Some((index, unexpected)) => {
+                Err(self.error(index, ArgsErrorKind::UnexpectedArgument(unexpected)))
+            }
+            None => Ok(matches),
+        }
+    }
+}
+
+/// The sub command named by the token, if no positional arguments were given yet.
+fn subcommand<'a, 'n>(
+    nodes: &'n [Node<'a>],
+    level: &Level<'a, 'n>,
+    token: &str,
+) -> Option<&'n Node<'a>> {
+    if !level.positionals.is_empty() {
+        return None;
+    }
+    let command = level.node.commands.iter().find(|command| {
+        command.name == token || command.aliases.iter().any(|alias| alias == token)
+    })?;
+    nodes.iter().find(|node| {
+        node.path.len() == level.node.path.len() + 1
+            && node.path.starts_with(&level.node.path)
+            && node
+                .command
+                .is_some_and(|node| std::ptr::eq(node, *command))
+    })
+}
+
+fn find_option<'a>(level: &Level<'a, '_>, name: &str) -> Option<&'a OpenCliOption> {
+    level
+        .node
+        .options
+        .iter()
+        .find(|option| option.name == name || option.aliases.iter().any(|alias| alias == name))
+        .copied()
+}
+
+/// The visible option names and aliases close to the unknown name, closest first.
+fn suggestions(level: &Level, name: &str) -> Vec<String> {
+    // Single letter names are too short for meaningful suggestions
+    let threshold = name.trim_start_matches('-').chars().count() / 3;
+    if threshold == 0 {
+        return Vec::new();
+    }
+    let mut candidates: Vec<(usize, &str)> = level
+        .node
+        .options
+        .iter()
+        .filter(|option| !option.hidden.unwrap_or(false))
+        .flat_map(|option| names(&option.name, &option.aliases))
+        .map(|candidate| (distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .collect();
+    candidates.sort_by_key(|(distance, _)| *distance);
+    candidates
+        .into_iter()
+        .map(|(_, candidate)| candidate.to_owned())
+        .collect()
+}
+
+/// The Levenshtein distance between two strings.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+fn looks_like_option(token: &str) -> bool {
+    token.starts_with('-') && token.len() > 1 && !is_number(token)
+}
+
+/// Whether the token is a negative decimal numeral like `-1` or `-0.5`, which is taken as a value.
+fn is_number(token: &str) -> bool {
+    let digits = token.strip_prefix('-').unwrap_or(token);
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    !(integer.is_empty() && fraction.is_empty())
+        && integer.chars().all(|c| c.is_ascii_digit())
+        && fraction.chars().all(|c| c.is_ascii_digit())
+}
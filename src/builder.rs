@@ -0,0 +1,356 @@
+use crate::{
+    OPENCLI_VERSION, OpenCliArgument, OpenCliArity, OpenCliCommand, OpenCliContact,
+    OpenCliConventions, OpenCliDocument, OpenCliExitCode, OpenCliInfo, OpenCliLicense,
+    OpenCliMetadata, OpenCliOption, ValidationError,
+};
+
+/// Builds an [`OpenCliDocument`] in code.
+///
+/// Created with [`OpenCliDocument::builder`]. The commands, options and arguments are built with
+/// the chainable methods on their own types, e.g. [`OpenCliCommand::new`].
+#[derive(Debug, Clone)]
+pub struct OpenCliDocumentBuilder {
+    document: OpenCliDocument,
+}
+
+impl OpenCliDocument {
+    /// Start building a document for the application with the given title and version.
+    pub fn builder(title: impl Into<String>, version: impl Into<String>) -> OpenCliDocumentBuilder {
+        OpenCliDocumentBuilder {
+            document: OpenCliDocument {
+                opencli: OPENCLI_VERSION.to_owned(),
+                info: OpenCliInfo {
+                    title: title.into(),
+                    version: version.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl OpenCliDocumentBuilder {
+    /// Set a short summary of the application.
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.document.info.summary = Some(summary.into());
+        self
+    }
+
+    /// Set the description of the application.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.document.info.description = Some(description.into());
+        self
+    }
+
+    /// Set the contact information.
+    pub fn contact(mut self, contact: OpenCliContact) -> Self {
+        self.document.info.contact = Some(contact);
+        self
+    }
+
+    /// Set the application license.
+    pub fn license(mut self, license: OpenCliLicense) -> Self {
+        self.document.info.license = Some(license);
+        self
+    }
+
+    /// Set the conventions used by the CLI.
+    pub fn conventions(mut self, conventions: OpenCliConventions) -> Self {
+        self.document.conventions = Some(conventions);
+        self
+    }
+
+    /// Add a root command argument.
+    pub fn argument(mut self, argument: OpenCliArgument) -> Self {
+        self.document.arguments.push(argument);
+        self
+    }
+
+    /// Add a root command option.
+    pub fn option(mut self, option: OpenCliOption) -> Self {
+        self.document.options.push(option);
+        self
+    }
+
+    /// Add a sub command.
+    pub fn command(mut self, command: OpenCliCommand) -> Self {
+        self.document.commands.push(command);
+        self
+    }
+
+    /// Add an exit code.
+    pub fn exit_code(mut self, exit_code: OpenCliExitCode) -> Self {
+        self.document.exit_codes.push(exit_code);
+        self
+    }
+
+    /// Add an example of how to use the CLI.
+    pub fn example(mut self, example: impl Into<String>) -> Self {
+        self.document.examples.push(example.into());
+        self
+    }
+
+    /// Set whether or not the CLI requires interactive input.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.document.interactive = Some(interactive);
+        self
+    }
+
+    /// Add custom metadata.
+    pub fn metadata(mut self, metadata: OpenCliMetadata) -> Self {
+        self.document.metadata.push(metadata);
+        self
+    }
+
+    /// Finish the document, checking it with [`OpenCliDocument::validate`].
+    ///
+    /// This catches empty required fields like names, `info.title` and `info.version`.
+    pub fn build(self) -> Result<OpenCliDocument, Vec<ValidationError>> {
+        self.document.validate()?;
+        Ok(self.document)
+    }
+}
+
+impl OpenCliCommand {
+    /// Create a command with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        OpenCliCommand {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add an alias.
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Add an option.
+    pub fn option(mut self, option: OpenCliOption) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    /// Add an argument.
+    pub fn argument(mut self, argument: OpenCliArgument) -> Self {
+        self.arguments.push(argument);
+        self
+    }
+
+    /// Add a sub command.
+    pub fn command(mut self, command: OpenCliCommand) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    /// Add an exit code.
+    pub fn exit_code(mut self, exit_code: OpenCliExitCode) -> Self {
+        self.exit_codes.push(exit_code);
+        self
+    }
+
+    /// Set the description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set whether or not the command is hidden.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = Some(hidden);
+        self
+    }
+
+    /// Add an example of how to use the command.
+    pub fn example(mut self, example: impl Into<String>) -> Self {
+        self.examples.push(example.into());
+        self
+    }
+
+    /// Set whether or not the command requires interactive input.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = Some(interactive);
+        self
+    }
+
+    /// Add custom metadata.
+    pub fn metadata(mut self, metadata: OpenCliMetadata) -> Self {
+        self.metadata.push(metadata);
+        self
+    }
+}
+
+impl OpenCliOption {
+    /// Create an option with the given name, e.g. `--output`.
+    ///
+    /// Values are added with [`argument`](Self::argument).
+    pub fn new(name: impl Into<String>) -> Self {
+        OpenCliOption {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Create an option which takes no values, e.g. `--verbose`.
+    pub fn flag(name: impl Into<String>) -> Self {
+        Self::new(name)
+    }
+
+    /// Add an alias, e.g. `-v`.
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Add an argument holding the values of the option.
+    pub fn argument(mut self, argument: OpenCliArgument) -> Self {
+        self.arguments.push(argument);
+        self
+    }
+
+    /// Set whether or not the option is required.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = Some(required);
+        self
+    }
+
+    /// Set the group.
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Set the description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set whether or not the option is available to sub commands.
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = Some(recursive);
+        self
+    }
+
+    /// Set whether or not the option is hidden.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = Some(hidden);
+        self
+    }
+
+    /// Add custom metadata.
+    pub fn metadata(mut self, metadata: OpenCliMetadata) -> Self {
+        self.metadata.push(metadata);
+        self
+    }
+}
+
+impl OpenCliArgument {
+    /// Create an argument with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        OpenCliArgument {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set whether or not the argument is required.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = Some(required);
+        self
+    }
+
+    /// Set the arity.
+    pub fn arity(mut self, arity: OpenCliArity) -> Self {
+        self.arity = Some(arity);
+        self
+    }
+
+    /// Set the accepted values.
+    pub fn accepted_values<I, T>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.accepted_values = values.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the group.
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Set the description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set whether or not the argument is hidden.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = Some(hidden);
+        self
+    }
+
+    /// Add custom metadata.
+    pub fn metadata(mut self, metadata: OpenCliMetadata) -> Self {
+        self.metadata.push(metadata);
+        self
+    }
+}
+
+impl OpenCliArity {
+    /// Exactly `count` values.
+    pub fn exactly(count: i32) -> Self {
+        Self::between(count, count)
+    }
+
+    /// At least `minimum` values, without an upper bound.
+    pub fn at_least(minimum: i32) -> Self {
+        OpenCliArity {
+            minimum: Some(minimum),
+            maximum: None,
+            ..Default::default()
+        }
+    }
+
+    /// At most `maximum` values, including none.
+    pub fn at_most(maximum: i32) -> Self {
+        Self::between(0, maximum)
+    }
+
+    /// Between `minimum` and `maximum` values, inclusive.
+    pub fn between(minimum: i32, maximum: i32) -> Self {
+        OpenCliArity {
+            minimum: Some(minimum),
+            maximum: Some(maximum),
+            ..Default::default()
+        }
+    }
+}
+
+impl OpenCliExitCode {
+    /// Create an exit code with a description.
+    pub fn new(code: i32, description: impl Into<String>) -> Self {
+        OpenCliExitCode {
+            code,
+            description: Some(description.into()),
+            ..Default::default()
+        }
+    }
+}
+
+impl OpenCliMetadata {
+    /// Create metadata with the given name and value.
+    pub fn new(name: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        OpenCliMetadata {
+            name: name.into(),
+            value: Some(value.into()),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,612 @@
+use std::fmt;
+
+use ::clap::builder::PossibleValuesParser;
+use ::clap::{Arg, ArgAction, Command};
+use thiserror::Error;
+
+use crate::tree::{shares_name, value_range};
+use crate::{
+    OPENCLI_VERSION, OpenCliArgument, OpenCliArity, OpenCliCommand, OpenCliDocument, OpenCliInfo,
+    OpenCliMetadata, OpenCliOption,
+};
+
+/// A part of an [`OpenCliDocument`] which could not be expressed as a [`clap::Command`](::clap::Command).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClapIncompatibility {
+    /// The location of the value, e.g. `commands[2].exitCodes`
+    pub path: String,
+
+    /// The kind of incompatibility
+    pub kind: ClapIncompatibilityKind,
+}
+
+/// The kinds of incompatibilities reported by [`OpenCliDocument::to_clap`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ClapIncompatibilityKind {
+    #[error("Not supported by clap")]
+    Unsupported,
+    #[error("Option name `{0}` is neither a long `--name` nor a short `-n` name")]
+    OptionName(String),
+    #[error("Option argument separator `{0}` is not supported")]
+    Separator(String),
+    #[error("Short options can always be grouped")]
+    GroupOptions,
+    #[error("The arguments of the option accept different values")]
+    AcceptedValues,
+    #[error("Option `{0}` shadows a recursive option")]
+    ShadowedOption(String),
+    #[error("Argument `{0}` follows an unbounded argument which is not the last one")]
+    AfterUnbounded(String),
+    #[error(
+        "Option and argument share the identifier `{0}`, the option is identified by its full name"
+    )]
+    ConflictingId(String),
+}
+
+impl fmt::Display for ClapIncompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+impl std::error::Error for ClapIncompatibility {}
+
+impl OpenCliDocument {
+    /// Describe a [`clap::Command`](::clap::Command) as an OpenCLI document.
+    ///
+    /// The command name and version become the document title and version. Sub commands map to
+    /// commands, positional args to arguments and all other args to options. Only what is set on
+    /// the command is described, so the `--help` and `--version` flags clap adds when building the
+    /// command are not included.
+    ///
+    /// Requires the `clap` feature.
+    pub fn from_clap(command: &Command) -> Self {
+        OpenCliDocument {
+            opencli: OPENCLI_VERSION.to_owned(),
+            info: OpenCliInfo {
+                title: command.get_name().to_owned(),
+                summary: command.get_about().map(ToString::to_string),
+                description: command.get_long_about().map(ToString::to_string),
+                version: command.get_version().unwrap_or_default().to_owned(),
+                ..Default::default()
+            },
+            arguments: arguments(command),
+            options: options(command),
+            commands: command.get_subcommands().map(convert_command).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Build a [`clap::Command`](::clap::Command) tree from the document.
+    ///
+    /// Options are identified by their name without leading dashes, e.g. `output` for `--output`,
+    /// arguments by their name. An option whose identifier is taken by an argument it applies to
+    /// is identified by its full name instead, e.g. `--target`. Everything clap cannot express,
+    /// like exit codes or metadata, is left out and listed in the returned incompatibilities.
+    ///
+    /// Requires the `clap` feature.
+    pub fn to_clap(&self) -> (Command, Vec<ClapIncompatibility>) {
+        let mut builder = Builder::default();
+
+        let mut command = Command::new(self.info.title.clone());
+        if !self.info.version.is_empty() {
+            command = command.version(self.info.version.clone());
+        }
+        if let Some(summary) = &self.info.summary {
+            command = command.about(summary.clone());
+        }
+        if let Some(description) = &self.info.description {
+            command = command.long_about(description.clone());
+        }
+        if let Some(contact) = &self.info.contact {
+            let author = match (&contact.name, &contact.email) {
+                (Some(name), Some(email)) => Some(format!("{name} <{email}>")),
+                (Some(name), None) => Some(name.clone()),
+                (None, Some(email)) => Some(email.clone()),
+                (None, None) => None,
+            };
+            if let Some(author) = author {
+                command = command.author(author);
+            }
+            if contact.url.is_some() {
+                builder.unsupported("info.contact.url".to_owned());
+            }
+        }
+        if self.info.license.is_some() {
+            builder.unsupported("info.license".to_owned());
+        }
+
+        if let Some(conventions) = &self.conventions {
+            if conventions.group_options == Some(false) {
+                builder.push(
+                    "conventions.groupOptions".to_owned(),
+                    ClapIncompatibilityKind::GroupOptions,
+                );
+            }
+            match conventions.option_argument_separator.as_deref() {
+                None | Some(" ") => {}
+                Some("=") => builder.require_equals = true,
+                Some(separator) => builder.push(
+                    "conventions.optionArgumentSeparator".to_owned(),
+                    ClapIncompatibilityKind::Separator(separator.to_owned()),
+                ),
+            }
+        }
+
+        let command = builder.command(
+            "",
+            command,
+            Level {
+                options: &self.options,
+                arguments: &self.arguments,
+                commands: &self.commands,
+                exit_codes: !self.exit_codes.is_empty(),
+                examples: &self.examples,
+                interactive: self.interactive.is_some(),
+                metadata: &self.metadata,
+            },
+            &[],
+        );
+        (command, builder.incompatibilities)
+    }
+}
+
+/// The parts of the root or a sub command which are converted the same way.
+struct Level<'a> {
+    options: &'a [OpenCliOption],
+    arguments: &'a [OpenCliArgument],
+    commands: &'a [OpenCliCommand],
+    exit_codes: bool,
+    examples: &'a [String],
+    interactive: bool,
+    metadata: &'a [OpenCliMetadata],
+}
+
+#[derive(Default)]
+struct Builder {
+    require_equals: bool,
+    incompatibilities: Vec<ClapIncompatibility>,
+}
+
+impl Builder {
+    fn push(&mut self, path: String, kind: ClapIncompatibilityKind) {
+        self.incompatibilities
+            .push(ClapIncompatibility { path, kind });
+    }
+
+    fn unsupported(&mut self, path: String) {
+        self.push(path, ClapIncompatibilityKind::Unsupported);
+    }
+
+    fn command(
+        &mut self,
+        path: &str,
+        mut command: Command,
+        level: Level,
+        inherited: &[&OpenCliOption],
+    ) -> Command {
+        if level.exit_codes {
+            self.unsupported(join(path, "exitCodes"));
+        }
+        if level.interactive {
+            self.unsupported(join(path, "interactive"));
+        }
+        if !level.metadata.is_empty() {
+            self.unsupported(join(path, "metadata"));
+        }
+        if !level.examples.is_empty() {
+            let mut examples = "Examples:".to_owned();
+            for example in level.examples {
+                for line in example.lines() {
+                    examples.push_str("\n  ");
+                    examples.push_str(line);
+                }
+            }
+            command = command.after_help(examples);
+        }
+
+        // Options named like the flags clap generates replace them
+        let names: Vec<&str> = inherited
+            .iter()
+            .copied()
+            .chain(level.options)
+            .flat_map(|option| std::iter::once(&option.name).chain(&option.aliases))
+            .map(String::as_str)
+            .collect();
+        if names.contains(&"--help") || names.contains(&"-h") {
+            command = command.disable_help_flag(true);
+        }
+        if names.contains(&"--version") || names.contains(&"-V") {
+            command = command.disable_version_flag(true);
+        }
+        if level.commands.iter().any(|command| {
+            command.name == "help" || command.aliases.iter().any(|alias| alias == "help")
+        }) {
+            command = command.disable_help_subcommand(true);
+        }
+
+        let mut recursive = inherited.to_vec();
+        for (i, option) in level.options.iter().enumerate() {
+            let option_path = join(path, &format!("options[{i}]"));
+            if inherited
+                .iter()
+                .any(|inherited| shares_name(inherited, option))
+            {
+                self.push(
+                    option_path,
+                    ClapIncompatibilityKind::ShadowedOption(option.name.clone()),
+                );
+                continue;
+            }
+            let recursive_option = option.recursive.unwrap_or(false);
+            let mut id = option.name.trim_start_matches('-');
+            if level.arguments.iter().any(|argument| argument.name == id)
+                || (recursive_option && nested_arguments(level.commands).any(|name| name == id))
+            {
+                self.push(
+                    join(&option_path, "name"),
+                    ClapIncompatibilityKind::ConflictingId(id.to_owned()),
+                );
+                id = &option.name;
+            }
+            if let Some(arg) = self.option(&option_path, option, id, path.is_empty()) {
+                command = command.arg(arg);
+                if recursive_option {
+                    recursive.push(option);
+                }
+            }
+        }
+
+        let mut after_unbounded = false;
+        for (i, argument) in level.arguments.iter().enumerate() {
+            let argument_path = join(path, &format!("arguments[{i}]"));
+            let mut arg = self.argument(&argument_path, argument);
+            if after_unbounded {
+                // Clap only allows values after an unbounded argument for the last one, following `--`
+                if i + 1 < level.arguments.len() {
+                    self.push(
+                        argument_path,
+                        ClapIncompatibilityKind::AfterUnbounded(argument.name.clone()),
+                    );
+                    continue;
+                }
+                arg = arg.last(true);
+            }
+            after_unbounded |= value_range(argument).1.is_none();
+            command = command.arg(arg);
+        }
+
+        for (i, sub) in level.commands.iter().enumerate() {
+            let sub_path = join(path, &format!("commands[{i}]"));
+            let mut subcommand = Command::new(sub.name.clone())
+                .visible_aliases(sub.aliases.clone())
+                .hide(sub.hidden.unwrap_or(false));
+            if let Some(description) = &sub.description {
+                subcommand = subcommand.about(description.clone());
+            }
+            let subcommand = self.command(
+                &sub_path,
+                subcommand,
+                Level {
+                    options: &sub.options,
+                    arguments: &sub.arguments,
+                    commands: &sub.commands,
+                    exit_codes: !sub.exit_codes.is_empty(),
+                    examples: &sub.examples,
+                    interactive: sub.interactive.is_some(),
+                    metadata: &sub.metadata,
+                },
+                &recursive,
+            );
+            command = command.subcommand(subcommand);
+        }
+
+        command
+    }
+
+    fn option(&mut self, path: &str, option: &OpenCliOption, id: &str, root: bool) -> Option<Arg> {
+        let mut arg = Arg::new(id.to_owned());
+        let mut named = false;
+        let names = std::iter::once((join(path, "name"), &option.name)).chain(
+            option
+                .aliases
+                .iter()
+                .enumerate()
+                .map(|(i, alias)| (join(path, &format!("aliases[{i}]")), alias)),
+        );
+        for (name_path, name) in names {
+            let short = name
+                .strip_prefix('-')
+                .filter(|short| short.chars().count() == 1 && *short != "-")
+                .and_then(|short| short.chars().next());
+            let long = name
+                .strip_prefix("--")
+                .filter(|long| !long.is_empty() && !long.starts_with('-'));
+            match (short, long) {
+                (Some(short), _) if arg.get_short().is_none() => arg = arg.short(short),
+                (Some(short), _) => arg = arg.visible_short_alias(short),
+                (_, Some(long)) if arg.get_long().is_none() => arg = arg.long(long.to_owned()),
+                (_, Some(long)) => arg = arg.visible_alias(long.to_owned()),
+                (None, None) => {
+                    self.push(name_path, ClapIncompatibilityKind::OptionName(name.clone()));
+                    continue;
+                }
+            }
+            named = true;
+        }
+        if !named {
+            return None;
+        }
+
+        if let Some(description) = &option.description {
+            arg = arg.help(description.clone());
+        }
+        if let Some(group) = &option.group {
+            arg = arg.help_heading(group.clone());
+        }
+        if !option.metadata.is_empty() {
+            self.unsupported(join(path, "metadata"));
+        }
+        arg = arg
+            .required(option.required.unwrap_or(false))
+            .hide(option.hidden.unwrap_or(false))
+            .global(option.recursive.unwrap_or(false));
+
+        let mut minimum = 0;
+        let mut maximum = Some(0);
+        let mut value_names = Vec::new();
+        for (i, argument) in option.arguments.iter().enumerate() {
+            let argument_path = join(path, &format!("arguments[{i}]"));
+            for (set, field) in [
+                (argument.description.is_some(), "description"),
+                (argument.group.is_some(), "group"),
+                (argument.hidden.is_some(), "hidden"),
+                (!argument.metadata.is_empty(), "metadata"),
+            ] {
+                if set {
+                    self.unsupported(join(&argument_path, field));
+                }
+            }
+            let (argument_minimum, argument_maximum) = value_range(argument);
+            minimum += argument_minimum;
+            maximum = maximum.zip(argument_maximum).map(|(a, b)| a + b);
+            if argument_maximum != Some(0) {
+                value_names.push(argument.name.clone());
+            }
+        }
+
+        if maximum == Some(0) {
+            let action = match option.name.as_str() {
+                "--help" => ArgAction::Help,
+                // Clap requires a version on the command for the version action
+                "--version" if root => ArgAction::Version,
+                _ => ArgAction::SetTrue,
+            };
+            return Some(arg.action(action));
+        }
+
+        arg = arg.action(ArgAction::Set).value_names(value_names);
+        arg = match maximum {
+            Some(maximum) => arg.num_args(minimum..=maximum),
+            None => arg.num_args(minimum..),
+        };
+        if self.require_equals {
+            arg = arg.require_equals(true);
+        }
+
+        let mut accepted_values = option
+            .arguments
+            .iter()
+            .filter(|argument| value_range(argument).1 != Some(0))
+            .map(|argument| &argument.accepted_values);
+        if let Some(first) = accepted_values.next() {
+            if accepted_values.all(|values| values == first) {
+                if !first.is_empty() {
+                    arg = arg.value_parser(PossibleValuesParser::new(first.clone()));
+                }
+            } else {
+                self.push(path.to_owned(), ClapIncompatibilityKind::AcceptedValues);
+            }
+        }
+        Some(arg)
+    }
+
+    fn argument(&mut self, path: &str, argument: &OpenCliArgument) -> Arg {
+        let mut arg = Arg::new(argument.name.clone())
+            .value_name(argument.name.clone())
+            .required(argument.required.unwrap_or(false))
+            .hide(argument.hidden.unwrap_or(false));
+        if let Some(description) = &argument.description {
+            arg = arg.help(description.clone());
+        }
+        if let Some(group) = &argument.group {
+            arg = arg.help_heading(group.clone());
+        }
+        if !argument.accepted_values.is_empty() {
+            arg = arg.value_parser(PossibleValuesParser::new(argument.accepted_values.clone()));
+        }
+        if !argument.metadata.is_empty() {
+            self.unsupported(join(path, "metadata"));
+        }
+        match value_range(argument) {
+            (1, Some(1)) => arg,
+            (minimum, Some(maximum)) => arg.num_args(minimum..=maximum),
+            (minimum, None) => arg.num_args(minimum..),
+        }
+    }
+}
+
+/// The names of the arguments of the given commands and all their sub commands.
+fn nested_arguments(commands: &[OpenCliCommand]) -> Box<dyn Iterator<Item = &str> + '_> {
+    Box::new(commands.iter().flat_map(|command| {
+        command
+            .arguments
+            .iter()
+            .map(|argument| argument.name.as_str())
+            .chain(nested_arguments(&command.commands))
+    }))
+}
+
+fn join(prefix: &str, segment: &str) -> String {
+    if prefix.is_empty() {
+        segment.to_owned()
+    } else {
+        format!("{prefix}.{segment}")
+    }
+}
+
+fn convert_command(command: &Command) -> OpenCliCommand {
+    OpenCliCommand {
+        name: command.get_name().to_owned(),
+        aliases: command.get_all_aliases().map(str::to_owned).collect(),
+        options: options(command),
+        arguments: arguments(command),
+        commands: command.get_subcommands().map(convert_command).collect(),
+        description: command
+            .get_long_about()
+            .or(command.get_about())
+            .map(ToString::to_string),
+        hidden: flag(command.is_hide_set()),
+        ..Default::default()
+    }
+}
+
+fn arguments(command: &Command) -> Vec<OpenCliArgument> {
+    command
+        .get_positionals()
+        .map(|arg| OpenCliArgument {
+            name: value_name(arg),
+            required: flag(arg.is_required_set()),
+            arity: arity(arg),
+            accepted_values: accepted_values(arg),
+            group: arg.get_help_heading().map(str::to_owned),
+            description: description(arg),
+            hidden: flag(arg.is_hide_set()),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn options(command: &Command) -> Vec<OpenCliOption> {
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_positional())
+        .map(|arg| {
+            let long = arg.get_long().map(|long| format!("--{long}"));
+            let short = arg.get_short().map(|short| format!("-{short}"));
+            let long_aliases = arg.get_all_aliases().unwrap_or_default();
+            let short_aliases = arg.get_all_short_aliases().unwrap_or_default();
+            let mut names = long
+                .into_iter()
+                .chain(short)
+                .chain(long_aliases.into_iter().map(|alias| format!("--{alias}")))
+                .chain(short_aliases.into_iter().map(|alias| format!("-{alias}")));
+
+            OpenCliOption {
+                name: names.next().unwrap_or_else(|| arg.get_id().to_string()),
+                required: flag(arg.is_required_set()),
+                aliases: names.collect(),
+                arguments: option_arguments(arg),
+                group: arg.get_help_heading().map(str::to_owned),
+                description: description(arg),
+                hidden: flag(arg.is_hide_set()),
+                recursive: flag(arg.is_global_set()),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn option_arguments(arg: &Arg) -> Vec<OpenCliArgument> {
+    if !takes_values(arg) {
+        return Vec::new();
+    }
+
+    // Several value names with a matching fixed count describe one value each, e.g. `<KEY> <VALUE>`
+    let value_names = arg.get_value_names().unwrap_or_default();
+    let fixed = arg
+        .get_num_args()
+        .filter(|range| range.min_values() == range.max_values())
+        .map(|range| range.min_values());
+    if value_names.len() > 1 && fixed == Some(value_names.len()) {
+        return value_names
+            .iter()
+            .map(|name| OpenCliArgument {
+                name: name.to_string(),
+                required: Some(true),
+                accepted_values: accepted_values(arg),
+                ..Default::default()
+            })
+            .collect();
+    }
+
+    vec![OpenCliArgument {
+        name: value_name(arg),
+        required: Some(
+            arg.get_num_args()
+                .is_none_or(|range| range.min_values() > 0),
+        ),
+        arity: arity(arg),
+        accepted_values: accepted_values(arg),
+        ..Default::default()
+    }]
+}
+
+fn takes_values(arg: &Arg) -> bool {
+    match arg.get_num_args() {
+        Some(range) => range.takes_values(),
+        None => arg.get_action().takes_values(),
+    }
+}
+
+/// The arity of the values, `None` for the default of exactly one value.
+fn arity(arg: &Arg) -> Option<OpenCliArity> {
+    let (minimum, maximum) = match arg.get_num_args() {
+        Some(range) => (
+            range.min_values(),
+            Some(range.max_values()).filter(|&maximum| maximum != usize::MAX),
+        ),
+        // Clap makes appending positionals take one or more values
+        None if arg.is_positional() && matches!(arg.get_action(), ArgAction::Append) => (1, None),
+        None => return None,
+    };
+    if (minimum, maximum) == (1, Some(1)) {
+        return None;
+    }
+    Some(OpenCliArity {
+        minimum: Some(clamp(minimum)),
+        maximum: maximum.map(clamp),
+        ..Default::default()
+    })
+}
+
+fn clamp(value: usize) -> i32 {
+    i32::try_from(value).unwrap_or(i32::MAX)
+}
+
+/// The value name of an arg, falling back to its id.
+fn value_name(arg: &Arg) -> String {
+    arg.get_value_names()
+        .and_then(|names| names.first())
+        .map(ToString::to_string)
+        .unwrap_or_else(|| arg.get_id().to_string())
+}
+
+fn accepted_values(arg: &Arg) -> Vec<String> {
+    arg.get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| value.get_name().to_owned())
+        .collect()
+}
+
+fn description(arg: &Arg) -> Option<String> {
+    arg.get_long_help()
+        .or(arg.get_help())
+        .map(ToString::to_string)
+}
+
+/// Only set boolean fields are written, keeping the document free of `false` noise.
+fn flag(value: bool) -> Option<bool> {
+    value.then_some(true)
+}
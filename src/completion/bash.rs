@@ -0,0 +1,108 @@
+use std::fmt::Write;
+
+use super::{identifier, path_key};
+use crate::tree::{Node, option_values, takes_value};
+
+pub(super) fn generate(program: &str, nodes: &[Node]) -> String {
+    let function = format!("_{}", identifier(program));
+    let mut script = String::new();
+
+    writeln!(script, "{function}() {{").unwrap();
+    writeln!(script, "    local cur prev cmd i").unwrap();
+    writeln!(script, "    COMPREPLY=()").unwrap();
+    writeln!(script, "    cur=\"${{COMP_WORDS[COMP_CWORD]}}\"").unwrap();
+    writeln!(script, "    prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"").unwrap();
+    writeln!(script, "    cmd={}", quote(&path_key(program, &[]))).unwrap();
+    writeln!(script).unwrap();
+
+    writeln!(script, "    for ((i = 1; i < COMP_CWORD; i++)); do").unwrap();
+    writeln!(script, "        case \"${{cmd}},${{COMP_WORDS[i]}}\" in").unwrap();
+    for node in nodes {
+        let parent = path_key(program, &node.path);
+        for command in &node.commands {
+            let mut path = node.path.clone();
+            path.push(&command.name);
+            let patterns: Vec<String> = std::iter::once(&command.name)
+                .chain(&command.aliases)
+                .map(|name| quote(&format!("{parent},{name}")))
+                .collect();
+            writeln!(script, "            {})", patterns.join("|")).unwrap();
+            writeln!(
+                script,
+                "                cmd={}",
+                quote(&path_key(program, &path))
+            )
+            .unwrap();
+            writeln!(script, "                ;;").unwrap();
+        }
+    }
+    writeln!(script, "        esac").unwrap();
+    writeln!(script, "    done").unwrap();
+    writeln!(script).unwrap();
+
+    writeln!(script, "    case \"${{cmd}}\" in").unwrap();
+    for node in nodes {
+        writeln!(script, "        {})", quote(&path_key(program, &node.path))).unwrap();
+
+        let value_options: Vec<_> = node
+            .options
+            .iter()
+            .filter(|option| takes_value(option))
+            .collect();
+        if !value_options.is_empty() {
+            writeln!(script, "            case \"${{prev}}\" in").unwrap();
+            for option in value_options {
+                let patterns: Vec<String> = std::iter::once(&option.name)
+                    .chain(&option.aliases)
+                    .map(|name| quote(name))
+                    .collect();
+                writeln!(script, "                {})", patterns.join("|")).unwrap();
+                let values = option_values(option);
+                if !values.is_empty() {
+                    writeln!(script, "                    {}", compgen(values)).unwrap();
+                }
+                writeln!(script, "                    return 0").unwrap();
+                writeln!(script, "                    ;;").unwrap();
+            }
+            writeln!(script, "            esac").unwrap();
+        }
+
+        writeln!(script, "            if [[ \"${{cur}}\" == -* ]]; then").unwrap();
+        writeln!(script, "                {}", compgen(node.option_names())).unwrap();
+        writeln!(script, "            else").unwrap();
+        writeln!(
+            script,
+            "                {}",
+            compgen(node.command_names().chain(node.argument_values()))
+        )
+        .unwrap();
+        writeln!(script, "            fi").unwrap();
+        writeln!(script, "            return 0").unwrap();
+        writeln!(script, "            ;;").unwrap();
+    }
+    writeln!(script, "    esac").unwrap();
+    writeln!(script, "}}").unwrap();
+    writeln!(script).unwrap();
+
+    writeln!(
+        script,
+        "complete -F {function} -o bashdefault -o default {}",
+        quote(program)
+    )
+    .unwrap();
+
+    script
+}
+
+fn compgen<'a>(words: impl IntoIterator<Item = &'a str>) -> String {
+    let words: Vec<&str> = words.into_iter().collect();
+    format!(
+        "COMPREPLY=($(compgen -W {} -- \"${{cur}}\"))",
+        quote(&words.join(" "))
+    )
+}
+
+/// Quote a word for bash using single quotes.
+fn quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
@@ -0,0 +1,114 @@
+use std::fmt::Write;
+
+use super::{identifier, path_key};
+use crate::OpenCliOption;
+use crate::tree::{Node, option_values, takes_value};
+
+pub(super) fn generate(program: &str, nodes: &[Node]) -> String {
+    let prefix = format!("__fish_{}", identifier(program));
+    let mut script = String::new();
+
+    writeln!(script, "function {prefix}_command_path").unwrap();
+    writeln!(script, "    set -l path {}", quote(&path_key(program, &[]))).unwrap();
+    writeln!(script, "    for token in (commandline -opc)[2..-1]").unwrap();
+    writeln!(script, "        switch \"$path,$token\"").unwrap();
+    for node in nodes {
+        let parent = path_key(program, &node.path);
+        for command in &node.commands {
+            let mut path = node.path.clone();
+            path.push(&command.name);
+            let patterns: Vec<String> = std::iter::once(&command.name)
+                .chain(&command.aliases)
+                .map(|name| quote(&format!("{parent},{name}")))
+                .collect();
+            writeln!(script, "            case {}", patterns.join(" ")).unwrap();
+            writeln!(
+                script,
+                "                set path {}",
+                quote(&path_key(program, &path))
+            )
+            .unwrap();
+        }
+    }
+    writeln!(script, "        end").unwrap();
+    writeln!(script, "    end").unwrap();
+    writeln!(script, "    echo $path").unwrap();
+    writeln!(script, "end").unwrap();
+    writeln!(script).unwrap();
+
+    writeln!(script, "function {prefix}_at").unwrap();
+    writeln!(script, "    test ({prefix}_command_path) = $argv[1]").unwrap();
+    writeln!(script, "end").unwrap();
+
+    for node in nodes {
+        let condition = quote(&format!(
+            "{prefix}_at {}",
+            quote(&path_key(program, &node.path))
+        ));
+        let complete = format!("complete -c {} -n {condition}", quote(program));
+
+        writeln!(script).unwrap();
+        for command in &node.commands {
+            for name in std::iter::once(&command.name).chain(&command.aliases) {
+                write!(script, "{complete} -f -a {}", quote(name)).unwrap();
+                if let Some(description) = &command.description {
+                    write!(script, " -d {}", quote(description)).unwrap();
+                }
+                writeln!(script).unwrap();
+            }
+        }
+        for option in &node.options {
+            writeln!(script, "{complete}{}", option_flags(option)).unwrap();
+        }
+        for argument in &node.arguments {
+            if argument.accepted_values.is_empty() {
+                continue;
+            }
+            write!(
+                script,
+                "{complete} -f -a {}",
+                quote(&argument.accepted_values.join(" "))
+            )
+            .unwrap();
+            if let Some(description) = &argument.description {
+                write!(script, " -d {}", quote(description)).unwrap();
+            }
+            writeln!(script).unwrap();
+        }
+    }
+
+    script
+}
+
+fn option_flags(option: &OpenCliOption) -> String {
+    let mut flags = String::new();
+    for name in std::iter::once(&option.name).chain(&option.aliases) {
+        if let Some(long) = name.strip_prefix("--") {
+            write!(flags, " -l {}", quote(long)).unwrap();
+        } else if let Some(short) = name.strip_prefix('-') {
+            if short.chars().count() == 1 {
+                write!(flags, " -s {}", quote(short)).unwrap();
+            } else {
+                write!(flags, " -o {}", quote(short)).unwrap();
+            }
+        } else {
+            write!(flags, " -o {}", quote(name)).unwrap();
+        }
+    }
+    if takes_value(option) {
+        flags.push_str(" -r");
+        let values = option_values(option);
+        if !values.is_empty() {
+            write!(flags, " -f -a {}", quote(&values.join(" "))).unwrap();
+        }
+    }
+    if let Some(description) = &option.description {
+        write!(flags, " -d {}", quote(description)).unwrap();
+    }
+    flags
+}
+
+/// Quote a word for fish using single quotes.
+fn quote(word: &str) -> String {
+    format!("'{}'", word.replace('\\', r"\\").replace('\'', r"\'"))
+}
@@ -0,0 +1,59 @@
+//! Shell completion script generation.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use opencli::{OpenCliDocument, completion::{self, Shell}};
+//!
+//! let opencli = OpenCliDocument::from_path("path/to/opencli.yaml").unwrap();
+//! let script = completion::generate(&opencli, Shell::Bash);
+//! ```
+//!
+//! The program name used by the generated scripts is taken from [`OpenCliInfo::title`](crate::OpenCliInfo::title).
+
+use crate::OpenCliDocument;
+use crate::tree::Node;
+
+mod bash;
+mod fish;
+mod powershell;
+mod zsh;
+
+/// The shells supported by [`generate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shell {
+    /// Bourne Again SHell
+    Bash,
+    /// Z shell
+    Zsh,
+    /// Friendly interactive shell
+    Fish,
+    /// PowerShell
+    PowerShell,
+}
+
+/// Generate a completion script for the given shell.
+pub fn generate(document: &OpenCliDocument, shell: Shell) -> String {
+    let nodes = Node::collect(document, false);
+    match shell {
+        Shell::Bash => bash::generate(&document.info.title, &nodes),
+        Shell::Zsh => zsh::generate(document, &nodes),
+        Shell::Fish => fish::generate(&document.info.title, &nodes),
+        Shell::PowerShell => powershell::generate(&document.info.title, &nodes),
+    }
+}
+
+/// A unique key for a command path, used to track the current command in the generated scripts.
+fn path_key(program: &str, path: &[&str]) -> String {
+    std::iter::once(program)
+        .chain(path.iter().copied())
+        .collect::<Vec<_>>()
+        .join("__")
+}
+
+/// Turn a program or command name into a valid shell identifier.
+fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
@@ -0,0 +1,178 @@
+use std::fmt::Write;
+
+use super::path_key;
+use crate::tree::{Node, option_values, takes_value};
+
+pub(super) fn generate(program: &str, nodes: &[Node]) -> String {
+    let mut script = String::new();
+
+    writeln!(script, "using namespace System.Management.Automation").unwrap();
+    writeln!(
+        script,
+        "using namespace System.Management.Automation.Language"
+    )
+    .unwrap();
+    writeln!(script).unwrap();
+    writeln!(
+        script,
+        "Register-ArgumentCompleter -Native -CommandName {} -ScriptBlock {{",
+        quote(program)
+    )
+    .unwrap();
+    writeln!(
+        script,
+        "    param($wordToComplete, $commandAst, $cursorPosition)"
+    )
+    .unwrap();
+    writeln!(script).unwrap();
+    writeln!(script, "    $commandElements = $commandAst.CommandElements").unwrap();
+    writeln!(script, "    $command = {}", quote(&path_key(program, &[]))).unwrap();
+    writeln!(
+        script,
+        "    for ($i = 1; $i -lt $commandElements.Count; $i++) {{"
+    )
+    .unwrap();
+    writeln!(script, "        $element = $commandElements[$i]").unwrap();
+    writeln!(
+        script,
+        "        if ($element -isnot [StringConstantExpressionAst] -or"
+    )
+    .unwrap();
+    writeln!(
+        script,
+        "            $element.StringConstantType -ne [StringConstantType]::BareWord -or"
+    )
+    .unwrap();
+    writeln!(script, "            $element.Value.StartsWith('-') -or").unwrap();
+    writeln!(script, "            $element.Value -eq $wordToComplete) {{").unwrap();
+    writeln!(script, "            continue").unwrap();
+    writeln!(script, "        }}").unwrap();
+    writeln!(script, "        switch (\"$command;$($element.Value)\") {{").unwrap();
+    for node in nodes {
+        let parent = path_key(program, &node.path);
+        for command in &node.commands {
+            let mut path = node.path.clone();
+            path.push(&command.name);
+            for name in std::iter::once(&command.name).chain(&command.aliases) {
+                writeln!(
+                    script,
+                    "            {} {{ $command = {}; break }}",
+                    quote(&format!("{parent};{name}")),
+                    quote(&path_key(program, &path))
+                )
+                .unwrap();
+            }
+        }
+    }
+    writeln!(script, "        }}").unwrap();
+    writeln!(script, "    }}").unwrap();
+    writeln!(script).unwrap();
+    writeln!(script, "    $previous = ''").unwrap();
+    writeln!(
+        script,
+        "    $last = if ($wordToComplete) {{ $commandElements.Count - 2 }} else {{ $commandElements.Count - 1 }}"
+    )
+    .unwrap();
+    writeln!(
+        script,
+        "    if ($last -ge 1) {{ $previous = $commandElements[$last].Extent.Text }}"
+    )
+    .unwrap();
+    writeln!(script).unwrap();
+
+    writeln!(script, "    $completions = @(switch ($command) {{").unwrap();
+    for node in nodes {
+        writeln!(
+            script,
+            "        {} {{",
+            quote(&path_key(program, &node.path))
+        )
+        .unwrap();
+
+        for option in node.options.iter().filter(|option| takes_value(option)) {
+            let names: Vec<String> = std::iter::once(&option.name)
+                .chain(&option.aliases)
+                .map(|name| quote(name))
+                .collect();
+            writeln!(
+                script,
+                "            if ($previous -in {}) {{",
+                names.join(", ")
+            )
+            .unwrap();
+            for value in option_values(option) {
+                writeln!(
+                    script,
+                    "                {}",
+                    result(value, "ParameterValue", None)
+                )
+                .unwrap();
+            }
+            writeln!(script, "                break").unwrap();
+            writeln!(script, "            }}").unwrap();
+        }
+
+        for option in &node.options {
+            for name in std::iter::once(&option.name).chain(&option.aliases) {
+                writeln!(
+                    script,
+                    "            {}",
+                    result(name, "ParameterName", option.description.as_deref())
+                )
+                .unwrap();
+            }
+        }
+        for command in &node.commands {
+            for name in std::iter::once(&command.name).chain(&command.aliases) {
+                writeln!(
+                    script,
+                    "            {}",
+                    result(name, "ParameterValue", command.description.as_deref())
+                )
+                .unwrap();
+            }
+        }
+        for argument in &node.arguments {
+            for value in &argument.accepted_values {
+                writeln!(
+                    script,
+                    "            {}",
+                    result(value, "ParameterValue", argument.description.as_deref())
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(script, "            break").unwrap();
+        writeln!(script, "        }}").unwrap();
+    }
+    writeln!(script, "    }})").unwrap();
+    writeln!(script).unwrap();
+    writeln!(
+        script,
+        "    $completions.Where{{ $_.CompletionText -like \"$wordToComplete*\" }} |"
+    )
+    .unwrap();
+    writeln!(script, "        Sort-Object -Property ListItemText").unwrap();
+    writeln!(script, "}}").unwrap();
+
+    script
+}
+
+fn result(text: &str, kind: &str, description: Option<&str>) -> String {
+    // The tooltip of a completion result must not be empty
+    let tooltip = description
+        .filter(|description| !description.is_empty())
+        .unwrap_or(text);
+    format!(
+        "[CompletionResult]::new({}, {}, [CompletionResultType]::{kind}, {})",
+        quote(text),
+        quote(text),
+        quote(tooltip)
+    )
+}
+
+/// Quote a string for PowerShell using single quotes.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
@@ -0,0 +1,337 @@
+use std::fmt::Write;
+
+use super::identifier;
+use crate::tree::{Node, group_options, value_range};
+use crate::{OpenCliArgument, OpenCliDocument, OpenCliOption};
+
+pub(super) fn generate(document: &OpenCliDocument, nodes: &[Node]) -> String {
+    let program = &document.info.title;
+    let separator = document
+        .conventions
+        .as_ref()
+        .and_then(|conventions| conventions.option_argument_separator.as_deref());
+    let group = group_options(
+        &document
+            .conventions
+            .as_ref()
+            .and_then(|conventions| conventions.group_options),
+    );
+
+    let mut script = String::new();
+    writeln!(script, "#compdef {}", word(program)).unwrap();
+
+    for node in nodes {
+        writeln!(script).unwrap();
+        function(&mut script, program, separator, group, node);
+    }
+
+    writeln!(script).unwrap();
+    writeln!(
+        script,
+        "if [ \"$funcstack[1]\" = \"{}\" ]; then",
+        function_name(program, &[])
+    )
+    .unwrap();
+    writeln!(script, "    {} \"$@\"", function_name(program, &[])).unwrap();
+    writeln!(script, "else").unwrap();
+    writeln!(
+        script,
+        "    compdef {} {}",
+        function_name(program, &[]),
+        quote(program)
+    )
+    .unwrap();
+    writeln!(script, "fi").unwrap();
+
+    script
+}
+
+fn function(script: &mut String, program: &str, separator: Option<&str>, group: bool, node: &Node) {
+    writeln!(script, "{}() {{", function_name(program, &node.path)).unwrap();
+    writeln!(
+        script,
+        "    local curcontext=\"$curcontext\" state line ret=1"
+    )
+    .unwrap();
+    writeln!(script, "    typeset -A opt_args").unwrap();
+    writeln!(script).unwrap();
+
+    // `-s` lets short options be grouped, e.g. `-vq`
+    let flags = if group { "-C -s" } else { "-C" };
+    writeln!(script, "    _arguments {flags} \\").unwrap();
+    for option in &node.options {
+        for spec in option_specs(option, separator) {
+            writeln!(script, "        {} \\", quote(&spec)).unwrap();
+        }
+    }
+    if node.commands.is_empty() {
+        for spec in argument_specs(&node.arguments) {
+            writeln!(script, "        {} \\", quote(&spec)).unwrap();
+        }
+    } else {
+        writeln!(script, "        {} \\", quote(": :->command")).unwrap();
+        writeln!(script, "        {} \\", quote("*:: :->args")).unwrap();
+    }
+    writeln!(script, "        && ret=0").unwrap();
+
+    if !node.commands.is_empty() {
+        let parent = std::iter::once(program)
+            .chain(node.path.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        writeln!(script).unwrap();
+        writeln!(script, "    case $state in").unwrap();
+        writeln!(script, "        command)").unwrap();
+        writeln!(script, "            local -a commands").unwrap();
+        writeln!(script, "            commands=(").unwrap();
+        for command in &node.commands {
+            for name in std::iter::once(&command.name).chain(&command.aliases) {
+                writeln!(
+                    script,
+                    "                {}",
+                    quote(&describe_entry(name, command.description.as_deref()))
+                )
+                .unwrap();
+            }
+        }
+        writeln!(script, "            )").unwrap();
+        writeln!(
+            script,
+            "            _describe -t commands {} commands && ret=0",
+            quote(&format!("{parent} commands"))
+        )
+        .unwrap();
+        writeln!(script, "            ;;").unwrap();
+        writeln!(script, "        args)").unwrap();
+        writeln!(script, "            case $line[1] in").unwrap();
+        for command in &node.commands {
+            let mut path = node.path.clone();
+            path.push(&command.name);
+            let patterns: Vec<String> = std::iter::once(&command.name)
+                .chain(&command.aliases)
+                .map(|name| quote(name))
+                .collect();
+            writeln!(
+                script,
+                "                {}) {} && ret=0 ;;",
+                patterns.join("|"),
+                function_name(program, &path)
+            )
+            .unwrap();
+        }
+        writeln!(script, "            esac").unwrap();
+        writeln!(script, "            ;;").unwrap();
+        writeln!(script, "    esac").unwrap();
+    }
+
+    // Grouped options are excluded from the `_arguments` option list and offered
+    // separately, so they appear under their group heading
+    let mut groups: Vec<(&str, Vec<&OpenCliOption>)> = Vec::new();
+    for option in &node.options {
+        if let Some(group) = &option.group {
+            match groups.iter_mut().find(|(name, _)| name == group) {
+                Some((_, options)) => options.push(option),
+                None => groups.push((group, vec![option])),
+            }
+        }
+    }
+    if !groups.is_empty() {
+        writeln!(script).unwrap();
+        writeln!(script, "    if [[ $PREFIX == -* ]]; then").unwrap();
+        for (group, options) in groups {
+            let tag = format!("{}-options", identifier(group).to_lowercase());
+            writeln!(script, "        local -a {}", identifier(&tag)).unwrap();
+            writeln!(script, "        {}=(", identifier(&tag)).unwrap();
+            for option in options {
+                for name in std::iter::once(&option.name).chain(&option.aliases) {
+                    writeln!(
+                        script,
+                        "            {}",
+                        quote(&describe_entry(name, option.description.as_deref()))
+                    )
+                    .unwrap();
+                }
+            }
+            writeln!(script, "        )").unwrap();
+            writeln!(
+                script,
+                "        _describe -t {} {} {} && ret=0",
+                quote(&tag),
+                quote(group),
+                identifier(&tag)
+            )
+            .unwrap();
+        }
+        writeln!(script, "    fi").unwrap();
+    }
+
+    writeln!(script).unwrap();
+    writeln!(script, "    return ret").unwrap();
+    writeln!(script, "}}").unwrap();
+}
+
+/// The `_arguments` specs of an option, one per name.
+fn option_specs(option: &OpenCliOption, separator: Option<&str>) -> Vec<String> {
+    let names: Vec<&String> = std::iter::once(&option.name)
+        .chain(&option.aliases)
+        .collect();
+
+    let mut values = String::new();
+    let mut repeatable = false;
+    for argument in &option.arguments {
+        let (minimum, maximum) = value_range(argument);
+        let message = escape(&argument.name);
+        let action = action(argument);
+        for _ in 0..minimum {
+            write!(values, ":{message}:{action}").unwrap();
+        }
+        match maximum {
+            Some(maximum) => {
+                for _ in minimum..maximum {
+                    write!(values, "::{message}:{action}").unwrap();
+                }
+            }
+            None => {
+                repeatable = true;
+                if minimum == 0 {
+                    write!(values, "::{message}:{action}").unwrap();
+                }
+            }
+        }
+    }
+
+    let exclusion = if names.len() > 1 && !repeatable {
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        format!("({})", names.join(" "))
+    } else {
+        String::new()
+    };
+    let description = option
+        .description
+        .as_deref()
+        .map(|description| format!("[{}]", escape(description)))
+        .unwrap_or_default();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let mut spec = exclusion.clone();
+            if repeatable {
+                spec.push('*');
+            }
+            if option.group.is_some() {
+                spec.push('!');
+            }
+            spec.push_str(name);
+            if !values.is_empty() && separator == Some("=") && name.starts_with("--") {
+                spec.push('=');
+            }
+            spec.push_str(&description);
+            spec.push_str(&values);
+            spec
+        })
+        .collect()
+}
+
+/// The `_arguments` specs of the positional arguments.
+fn argument_specs(arguments: &[&OpenCliArgument]) -> Vec<String> {
+    let mut specs = Vec::new();
+    for argument in arguments {
+        let (minimum, maximum) = value_range(argument);
+        let minimum = if argument.required.unwrap_or(false) {
+            minimum.max(1)
+        } else {
+            0
+        };
+        let message = escape(&argument.name);
+        let action = action(argument);
+        for _ in 0..minimum {
+            specs.push(format!(":{message}:{action}"));
+        }
+        match maximum {
+            Some(maximum) => {
+                for _ in minimum..maximum {
+                    specs.push(format!("::{message}:{action}"));
+                }
+            }
+            None => {
+                // Nothing can follow an argument with an unbounded number of values
+                specs.push(format!("*::{message}:{action}"));
+                break;
+            }
+        }
+    }
+    specs
+}
+
+fn action(argument: &OpenCliArgument) -> String {
+    if argument.accepted_values.is_empty() {
+        "_default".to_owned()
+    } else {
+        let values: Vec<String> = argument
+            .accepted_values
+            .iter()
+            .map(|value| escape_value(value))
+            .collect();
+        format!("({})", values.join(" "))
+    }
+}
+
+fn describe_entry(name: &str, description: Option<&str>) -> String {
+    let name = name.replace(':', r"\:");
+    match description {
+        Some(description) => format!("{name}:{description}"),
+        None => name,
+    }
+}
+
+fn function_name(program: &str, path: &[&str]) -> String {
+    let segments: Vec<String> = std::iter::once(program)
+        .chain(path.iter().copied())
+        .map(identifier)
+        .collect();
+    format!("_{}", segments.join("__"))
+}
+
+/// Escape characters with a special meaning in `_arguments` specs.
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if matches!(c, '[' | ']' | ':' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape characters with a special meaning in `_arguments` value lists.
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+        if matches!(c, ' ' | '(' | ')' | ':' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Quote a word for zsh using single quotes.
+/// A shell word, quoted only if necessary.
+fn word(word: &str) -> String {
+    if !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+'))
+    {
+        word.to_owned()
+    } else {
+        quote(word)
+    }
+}
+
+fn quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
@@ -0,0 +1,933 @@
+//! Structural comparison of two documents.
+//!
+//! Commands and options are matched by name or alias, arguments by name, and exit codes by
+//! code, so reordering items is not reported as a change, except for arguments whose values are
+//! assigned by position. Every change is classified with a
+//! [`Severity`], and [`Diff::check_version`] tells whether the version bump between the two
+//! documents is large enough for the changes, following semantic versioning.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use opencli::OpenCliDocument;
+//!
+//! let old = OpenCliDocument::from_path("path/to/old.yaml").unwrap();
+//! let new = OpenCliDocument::from_path("path/to/new.yaml").unwrap();
+//! let diff = opencli::diff(&old, &new);
+//! print!("{diff}");
+//! println!("{}", serde_json::to_string_pretty(&diff).unwrap());
+//! if let Err(error) = diff.check_version() {
+//!     eprintln!("{error}");
+//! }
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::tree::{arity_range, group_options, option_argument_separator, value_range};
+use crate::{
+    OpenCliArgument, OpenCliArity, OpenCliCommand, OpenCliConventions, OpenCliDocument,
+    OpenCliExitCode, OpenCliOption,
+};
+
+/// The changes between two documents.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diff {
+    /// The `info.version` of the old document
+    pub old_version: String,
+
+    /// The `info.version` of the new document
+    pub new_version: String,
+
+    /// The changes, grouped by command in document order
+    pub changes: Vec<Change>,
+}
+
+/// A single change between two documents.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    /// The names of the commands leading to the changed item in the new document, empty for the root
+    pub command: Vec<String>,
+
+    /// The changed item
+    pub item: Item,
+
+    /// What changed
+    pub kind: ChangeKind,
+
+    /// How the change affects existing invocations
+    pub severity: Severity,
+}
+
+/// An item of a document which can change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Item {
+    /// The command itself, or the root for an empty command path
+    Command,
+    Option {
+        name: String,
+    },
+    Argument {
+        name: String,
+    },
+    OptionArgument {
+        option: String,
+        argument: String,
+    },
+    ExitCode {
+        code: i32,
+    },
+    /// The conventions of the document
+    Conventions,
+}
+
+/// The kinds of changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    /// The item was matched through an alias and its name changed
+    Renamed {
+        from: String,
+        to: String,
+    },
+    AliasAdded {
+        alias: String,
+    },
+    AliasRemoved {
+        alias: String,
+    },
+    /// The argument moved to another position, counting from 0
+    Moved {
+        from: usize,
+        to: usize,
+    },
+    RequiredChanged {
+        from: bool,
+        to: bool,
+    },
+    /// The option became available to sub commands or stopped being so
+    RecursiveChanged {
+        from: bool,
+        to: bool,
+    },
+    HiddenChanged {
+        from: bool,
+        to: bool,
+    },
+    ArityChanged {
+        from: Option<OpenCliArity>,
+        to: Option<OpenCliArity>,
+        direction: ArityDirection,
+    },
+    AcceptedValuesChanged {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    DescriptionChanged {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    GroupOptionsChanged {
+        from: bool,
+        to: bool,
+    },
+    SeparatorChanged {
+        from: String,
+        to: String,
+    },
+}
+
+/// How the range of accepted value counts changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArityDirection {
+    /// The new range includes the old one
+    Widened,
+    /// The old range includes the new one
+    Narrowed,
+    /// The ranges overlap only partially or not at all
+    Shifted,
+}
+
+/// How a change affects existing invocations of the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    /// Only the documentation changed, e.g. a description
+    Informational,
+    /// Existing invocations keep working, e.g. a new optional flag
+    NonBreaking,
+    /// Existing invocations may stop working, e.g. a removed option or a new required argument
+    Breaking,
+}
+
+/// The part of a semantic version which is incremented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Bump {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+/// The errors reported by [`Diff::check_version`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum VersionError {
+    #[error("Version `{0}` is not a semantic version")]
+    Invalid(String),
+    #[error(
+        "Version bump from `{old}` to `{new}` is too small, the changes require a {required} bump"
+    )]
+    TooSmall {
+        old: String,
+        new: String,
+        actual: Bump,
+        required: Bump,
+    },
+}
+
+impl Diff {
+    /// Whether the documents are structurally equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The highest severity of all changes, `None` if there are no changes.
+    pub fn severity(&self) -> Option<Severity> {
+        self.changes.iter().map(|change| change.severity).max()
+    }
+
+    /// The changes with the given severity.
+    pub fn changes_with(&self, severity: Severity) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(move |change| change.severity == severity)
+    }
+
+    /// The smallest version bump covering the changes.
+    ///
+    /// Breaking changes require a major bump, non-breaking changes a minor bump and informational
+    /// changes a patch bump.
+    pub fn required_bump(&self) -> Bump {
+        match self.severity() {
+            None => Bump::None,
+            Some(Severity::Informational) => Bump::Patch,
+            Some(Severity::NonBreaking) => Bump::Minor,
+            Some(Severity::Breaking) => Bump::Major,
+        }
+    }
+
+    /// Check that the bump from the old to the new `info.version` covers the changes.
+    ///
+    /// Versions are `MAJOR.MINOR.PATCH` with an optional `v` prefix, missing minor or patch parts
+    /// count as 0 and pre-release and build suffixes are ignored. As usual for versions before
+    /// 1.0.0, a minor bump of a `0.x` version counts as a major bump and a patch bump as a minor
+    /// one, while any bump of a `0.0.x` version counts as a major bump.
+    pub fn check_version(&self) -> Result<(), VersionError> {
+        let required = self.required_bump();
+        let actual = bump(&self.old_version, &self.new_version)?;
+        if actual < required {
+            return Err(VersionError::TooSmall {
+                old: self.old_version.clone(),
+                new: self.new_version.clone(),
+                actual,
+                required,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Informational => "informational",
+            Severity::NonBreaking => "non-breaking",
+            Severity::Breaking => "breaking",
+        })
+    }
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Bump::None => "none",
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        })
+    }
+}
+
+/// The bump between two versions, taking the `0.x` rules into account.
+fn bump(old: &str, new: &str) -> Result<Bump, VersionError> {
+    let (old, new) = (parse_version(old)?, parse_version(new)?);
+    let bump = if new <= old {
+        Bump::None
+    } else if new.0 != old.0 {
+        Bump::Major
+    } else if new.1 != old.1 {
+        Bump::Minor
+    } else {
+        Bump::Patch
+    };
+    Ok(match (old.0, old.1, bump) {
+        (0, 0, Bump::Patch | Bump::Minor) => Bump::Major,
+        (0, _, Bump::Minor) => Bump::Major,
+        (0, _, Bump::Patch) => Bump::Minor,
+        _ => bump,
+    })
+}
+
+fn parse_version(version: &str) -> Result<(u64, u64, u64), VersionError> {
+    let invalid = || VersionError::Invalid(version.to_owned());
+    let core = version.strip_prefix('v').unwrap_or(version);
+    let core = core.split(['-', '+']).next().unwrap_or_default();
+    let parts = core
+        .split('.')
+        .map(|part| part.parse::<u64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+    match parts[..] {
+        [major] => Ok((major, 0, 0)),
+        [major, minor] => Ok((major, minor, 0)),
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(invalid()),
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] ", self.severity)?;
+        if self.command.is_empty() {
+            write!(f, "<root>: ")?;
+        } else {
+            write!(f, "{}: ", self.command.join(" "))?;
+        }
+        match &self.item {
+            Item::Command if self.command.is_empty() => write!(f, "root")?,
+            Item::Command => write!(f, "command")?,
+            Item::Option { name } => write!(f, "option `{name}`")?,
+            Item::Argument { name } => write!(f, "argument `{name}`")?,
+            Item::OptionArgument { option, argument } => {
+                write!(f, "option `{option}` argument `{argument}`")?
+            }
+            Item::ExitCode { code } => write!(f, "exit code {code}")?,
+            Item::Conventions => write!(f, "conventions")?,
+        }
+        write!(f, " {}", self.kind)
+    }
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeKind::Added => write!(f, "added"),
+            ChangeKind::Removed => write!(f, "removed"),
+            ChangeKind::Renamed { from, to } => write!(f, "renamed from `{from}` to `{to}`"),
+            ChangeKind::AliasAdded { alias } => write!(f, "alias `{alias}` added"),
+            ChangeKind::AliasRemoved { alias } => write!(f, "alias `{alias}` removed"),
+            ChangeKind::Moved { from, to } => {
+                write!(f, "moved from position {} to {}", from + 1, to + 1)
+            }
+            ChangeKind::RequiredChanged { to: true, .. } => write!(f, "made required"),
+            ChangeKind::RequiredChanged { to: false, .. } => write!(f, "made optional"),
+            ChangeKind::RecursiveChanged { to: true, .. } => write!(f, "made recursive"),
+            ChangeKind::RecursiveChanged { to: false, .. } => write!(f, "made non-recursive"),
+            ChangeKind::HiddenChanged { to: true, .. } => write!(f, "hidden"),
+            ChangeKind::HiddenChanged { to: false, .. } => write!(f, "unhidden"),
+            ChangeKind::ArityChanged {
+                from,
+                to,
+                direction,
+            } => write!(
+                f,
+                "arity {} from {} to {}",
+                match direction {
+                    ArityDirection::Widened => "widened",
+                    ArityDirection::Narrowed => "narrowed",
+                    ArityDirection::Shifted => "changed",
+                },
+                arity(from),
+                arity(to)
+            ),
+            ChangeKind::AcceptedValuesChanged { added, removed } => {
+                write!(f, "accepted values changed")?;
+                if !added.is_empty() {
+                    write!(f, ", added {}", added.join(", "))?;
+                }
+                if !removed.is_empty() {
+                    write!(f, ", removed {}", removed.join(", "))?;
+                }
+                Ok(())
+            }
+            ChangeKind::DescriptionChanged { .. } => write!(f, "description changed"),
+            ChangeKind::GroupOptionsChanged { to: true, .. } => {
+                write!(f, "short option grouping enabled")
+            }
+            ChangeKind::GroupOptionsChanged { to: false, .. } => {
+                write!(f, "short option grouping disabled")
+            }
+            ChangeKind::SeparatorChanged { from, to } => {
+                write!(
+                    f,
+                    "option argument separator changed from `{from}` to `{to}`"
+                )
+            }
+        }
+    }
+}
+
+/// Format an arity like `1`, `0..1` or `1..`.
+fn arity(arity: &Option<OpenCliArity>) -> String {
+    match arity_range(arity) {
+        (minimum, Some(maximum)) if minimum == maximum => minimum.to_string(),
+        (minimum, Some(maximum)) => format!("{minimum}..{maximum}"),
+        (minimum, None) => format!("{minimum}.."),
+    }
+}
+
+/// Compare two documents.
+pub fn diff(old: &OpenCliDocument, new: &OpenCliDocument) -> Diff {
+    let mut differ = Differ::default();
+    differ.description(
+        &[],
+        Item::Command,
+        &old.info.description,
+        &new.info.description,
+    );
+    differ.conventions(old.conventions.as_ref(), new.conventions.as_ref());
+    differ.level(
+        &[],
+        Level {
+            options: &old.options,
+            arguments: &old.arguments,
+            commands: &old.commands,
+            exit_codes: &old.exit_codes,
+        },
+        Level {
+            options: &new.options,
+            arguments: &new.arguments,
+            commands: &new.commands,
+            exit_codes: &new.exit_codes,
+        },
+    );
+    Diff {
+        old_version: old.info.version.clone(),
+        new_version: new.info.version.clone(),
+        changes: differ.changes,
+    }
+}
+
+/// The items of the root or a command.
+struct Level<'a> {
+    options: &'a [OpenCliOption],
+    arguments: &'a [OpenCliArgument],
+    commands: &'a [OpenCliCommand],
+    exit_codes: &'a [OpenCliExitCode],
+}
+
+impl<'a> Level<'a> {
+    fn of(command: &'a OpenCliCommand) -> Self {
+        Level {
+            options: &command.options,
+            arguments: &command.arguments,
+            commands: &command.commands,
+            exit_codes: &command.exit_codes,
+        }
+    }
+}
+
+/// The items of two lists paired by name or alias.
+struct Matched<'a, T> {
+    pairs: Vec<(&'a T, &'a T)>,
+    removed: Vec<&'a T>,
+    added: Vec<&'a T>,
+}
+
+/// Pair the items with the same name first, then the items sharing any name or alias.
+fn match_items<'a, T>(
+    old: &'a [T],
+    new: &'a [T],
+    names: impl Fn(&T) -> (&str, &[String]),
+) -> Matched<'a, T> {
+    let mut paired: Vec<Option<usize>> = vec![None; old.len()];
+    let mut taken = vec![false; new.len()];
+
+    for (i, old_item) in old.iter().enumerate() {
+        let (name, _) = names(old_item);
+        if let Some(j) = (0..new.len()).find(|&j| !taken[j] && names(&new[j]).0 == name) {
+            paired[i] = Some(j);
+            taken[j] = true;
+        }
+    }
+    for (i, old_item) in old.iter().enumerate() {
+        if paired[i].is_some() {
+            continue;
+        }
+        let (old_name, old_aliases) = names(old_item);
+        let old_names: Vec<&str> = std::iter::once(old_name)
+            .chain(old_aliases.iter().map(String::as_str))
+            .collect();
+        let found = (0..new.len()).find(|&j| {
+            let (new_name, new_aliases) = names(&new[j]);
+            !taken[j]
+                && std::iter::once(new_name)
+                    .chain(new_aliases.iter().map(String::as_str))
+                    .any(|name| old_names.contains(&name))
+        });
+        if let Some(j) = found {
+            paired[i] = Some(j);
+            taken[j] = true;
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let mut removed = Vec::new();
+    for (i, old_item) in old.iter().enumerate() {
+        match paired[i] {
+            Some(j) => pairs.push((old_item, &new[j])),
+            None => removed.push(old_item),
+        }
+    }
+    let added = new
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| !taken[*j])
+        .map(|(_, item)| item)
+        .collect();
+    Matched {
+        pairs,
+        removed,
+        added,
+    }
+}
+
+#[derive(Default)]
+struct Differ {
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn push(&mut self, command: &[String], item: Item, kind: ChangeKind) {
+        let severity = severity(&kind);
+        self.push_as(command, item, kind, severity);
+    }
+
+    /// Push a change whose severity depends on more than its kind.
+    fn push_as(&mut self, command: &[String], item: Item, kind: ChangeKind, severity: Severity) {
+        self.changes.push(Change {
+            command: command.to_vec(),
+            item,
+            kind,
+            severity,
+        });
+    }
+
+    fn level(&mut self, path: &[String], old: Level, new: Level) {
+        let options = match_items(old.options, new.options, |option| {
+            (&option.name, &option.aliases)
+        });
+        for option in options.removed {
+            self.push(path, option_item(option), ChangeKind::Removed);
+        }
+        for option in options.added {
+            let severity = requirement(option.required.unwrap_or(false));
+            self.push_as(path, option_item(option), ChangeKind::Added, severity);
+        }
+        for (old_option, new_option) in options.pairs {
+            self.option(path, old_option, new_option);
+        }
+
+        let arguments = match_items(old.arguments, new.arguments, |argument| {
+            (&argument.name, &[])
+        });
+        for argument in arguments.removed {
+            self.push(path, argument_item(argument), ChangeKind::Removed);
+        }
+        for argument in arguments.added {
+            let severity = requirement(argument.required.unwrap_or(false));
+            self.push_as(path, argument_item(argument), ChangeKind::Added, severity);
+        }
+        for (old_argument, new_argument) in arguments.pairs {
+            let item = argument_item(new_argument);
+            self.position(
+                path,
+                item.clone(),
+                index(old.arguments, old_argument),
+                index(new.arguments, new_argument),
+            );
+            self.argument(path, item, old_argument, new_argument);
+        }
+
+        for exit_code in old.exit_codes {
+            if !new.exit_codes.iter().any(|new| new.code == exit_code.code) {
+                let item = Item::ExitCode {
+                    code: exit_code.code,
+                };
+                self.push(path, item, ChangeKind::Removed);
+            }
+        }
+        for exit_code in new.exit_codes {
+            let item = Item::ExitCode {
+                code: exit_code.code,
+            };
+            match old.exit_codes.iter().find(|old| old.code == exit_code.code) {
+                Some(old) => self.description(path, item, &old.description, &exit_code.description),
+                None => self.push(path, item, ChangeKind::Added),
+            }
+        }
+
+        let commands = match_items(old.commands, new.commands, |command| {
+            (&command.name, &command.aliases)
+        });
+        for command in commands.removed {
+            self.push(
+                &child(path, &command.name),
+                Item::Command,
+                ChangeKind::Removed,
+            );
+        }
+        for command in commands.added {
+            self.push(
+                &child(path, &command.name),
+                Item::Command,
+                ChangeKind::Added,
+            );
+        }
+        for (old_command, new_command) in commands.pairs {
+            let path = child(path, &new_command.name);
+            self.names(
+                &path,
+                Item::Command,
+                (&old_command.name, &old_command.aliases),
+                (&new_command.name, &new_command.aliases),
+            );
+            self.hidden(&path, Item::Command, old_command.hidden, new_command.hidden);
+            self.description(
+                &path,
+                Item::Command,
+                &old_command.description,
+                &new_command.description,
+            );
+            self.level(&path, Level::of(old_command), Level::of(new_command));
+        }
+    }
+
+    fn option(&mut self, path: &[String], old: &OpenCliOption, new: &OpenCliOption) {
+        let item = option_item(new);
+        self.names(
+            path,
+            item.clone(),
+            (&old.name, &old.aliases),
+            (&new.name, &new.aliases),
+        );
+        self.required(path, item.clone(), old.required, new.required);
+        let (from, to) = (
+            old.recursive.unwrap_or(false),
+            new.recursive.unwrap_or(false),
+        );
+        if from != to {
+            self.push(
+                path,
+                item.clone(),
+                ChangeKind::RecursiveChanged { from, to },
+            );
+        }
+        self.hidden(path, item.clone(), old.hidden, new.hidden);
+        self.description(path, item, &old.description, &new.description);
+
+        let arguments = match_items(&old.arguments, &new.arguments, |argument| {
+            (&argument.name, &[])
+        });
+        let option_argument = |argument: &OpenCliArgument| Item::OptionArgument {
+            option: new.name.clone(),
+            argument: argument.name.clone(),
+        };
+        for argument in arguments.removed {
+            self.push(path, option_argument(argument), ChangeKind::Removed);
+        }
+        for argument in arguments.added {
+            // Every use of the option has to pass the values of a new argument with a minimum
+            let severity = requirement(value_range(argument).0 > 0);
+            self.push_as(path, option_argument(argument), ChangeKind::Added, severity);
+        }
+        for (old_argument, new_argument) in arguments.pairs {
+            let item = option_argument(new_argument);
+            self.position(
+                path,
+                item.clone(),
+                index(&old.arguments, old_argument),
+                index(&new.arguments, new_argument),
+            );
+            self.argument(path, item, old_argument, new_argument);
+        }
+    }
+
+    fn argument(
+        &mut self,
+        path: &[String],
+        item: Item,
+        old: &OpenCliArgument,
+        new: &OpenCliArgument,
+    ) {
+        self.required(path, item.clone(), old.required, new.required);
+
+        let (old_range, new_range) = (arity_range(&old.arity), arity_range(&new.arity));
+        if old_range != new_range {
+            let contains = |outer: (usize, Option<usize>), inner: (usize, Option<usize>)| {
+                outer.0 <= inner.0
+                    && match (outer.1, inner.1) {
+                        (None, _) => true,
+                        (Some(_), None) => false,
+                        (Some(outer), Some(inner)) => outer >= inner,
+                    }
+            };
+            let direction = if contains(new_range, old_range) {
+                ArityDirection::Widened
+            } else if contains(old_range, new_range) {
+                ArityDirection::Narrowed
+            } else {
+                ArityDirection::Shifted
+            };
+            self.push(
+                path,
+                item.clone(),
+                ChangeKind::ArityChanged {
+                    from: old.arity.clone(),
+                    to: new.arity.clone(),
+                    direction,
+                },
+            );
+        }
+
+        let added: Vec<String> = new
+            .accepted_values
+            .iter()
+            .filter(|value| !old.accepted_values.contains(value))
+            .cloned()
+            .collect();
+        let removed: Vec<String> = old
+            .accepted_values
+            .iter()
+            .filter(|value| !new.accepted_values.contains(value))
+            .cloned()
+            .collect();
+        if !added.is_empty() || !removed.is_empty() {
+            // An argument without accepted values accepts anything
+            let severity = requirement(
+                !new.accepted_values.is_empty()
+                    && (old.accepted_values.is_empty() || !removed.is_empty()),
+            );
+            self.push_as(
+                path,
+                item.clone(),
+                ChangeKind::AcceptedValuesChanged { added, removed },
+                severity,
+            );
+        }
+
+        self.hidden(path, item.clone(), old.hidden, new.hidden);
+        self.description(path, item, &old.description, &new.description);
+    }
+
+    fn names(
+        &mut self,
+        path: &[String],
+        item: Item,
+        (old_name, old_aliases): (&String, &[String]),
+        (new_name, new_aliases): (&String, &[String]),
+    ) {
+        if old_name != new_name {
+            // Keeping the old name as an alias keeps existing invocations working
+            let severity = if new_aliases.contains(old_name) {
+                Severity::NonBreaking
+            } else {
+                Severity::Breaking
+            };
+            self.push_as(
+                path,
+                item.clone(),
+                ChangeKind::Renamed {
+                    from: old_name.clone(),
+                    to: new_name.clone(),
+                },
+                severity,
+            );
+        }
+        // Swapping the name with an alias keeps both accepted
+        for alias in old_aliases
+            .iter()
+            .filter(|alias| *alias != new_name && !new_aliases.contains(alias))
+        {
+            self.push(
+                path,
+                item.clone(),
+                ChangeKind::AliasRemoved {
+                    alias: alias.clone(),
+                },
+            );
+        }
+        for alias in new_aliases
+            .iter()
+            .filter(|alias| *alias != old_name && !old_aliases.contains(alias))
+        {
+            self.push(
+                path,
+                item.clone(),
+                ChangeKind::AliasAdded {
+                    alias: alias.clone(),
+                },
+            );
+        }
+    }
+
+    /// Values are assigned to arguments by position, so moving one changes their meaning.
+    fn position(&mut self, path: &[String], item: Item, from: usize, to: usize) {
+        if from != to {
+            self.push(path, item, ChangeKind::Moved { from, to });
+        }
+    }
+
+    /// Compare the conventions, taking the OpenCLI defaults into account.
+    fn conventions(&mut self, old: Option<&OpenCliConventions>, new: Option<&OpenCliConventions>) {
+        let group = |conventions: Option<&OpenCliConventions>| {
+            group_options(&conventions.and_then(|conventions| conventions.group_options))
+        };
+        let (from, to) = (group(old), group(new));
+        if from != to {
+            self.push(
+                &[],
+                Item::Conventions,
+                ChangeKind::GroupOptionsChanged { from, to },
+            );
+        }
+
+        let separator = |conventions: Option<&OpenCliConventions>| {
+            option_argument_separator(
+                &conventions.and_then(|conventions| conventions.option_argument_separator.clone()),
+            )
+            .to_owned()
+        };
+        let (from, to) = (separator(old), separator(new));
+        if from != to {
+            self.push(
+                &[],
+                Item::Conventions,
+                ChangeKind::SeparatorChanged { from, to },
+            );
+        }
+    }
+
+    fn required(&mut self, path: &[String], item: Item, old: Option<bool>, new: Option<bool>) {
+        let (from, to) = (old.unwrap_or(false), new.unwrap_or(false));
+        if from != to {
+            self.push(path, item, ChangeKind::RequiredChanged { from, to });
+        }
+    }
+
+    fn hidden(&mut self, path: &[String], item: Item, old: Option<bool>, new: Option<bool>) {
+        let (from, to) = (old.unwrap_or(false), new.unwrap_or(false));
+        if from != to {
+            self.push(path, item, ChangeKind::HiddenChanged { from, to });
+        }
+    }
+
+    fn description(
+        &mut self,
+        path: &[String],
+        item: Item,
+        old: &Option<String>,
+        new: &Option<String>,
+    ) {
+        if old != new {
+            self.push(
+                path,
+                item,
+                ChangeKind::DescriptionChanged {
+                    from: old.clone(),
+                    to: new.clone(),
+                },
+            );
+        }
+    }
+}
+
+fn option_item(option: &OpenCliOption) -> Item {
+    Item::Option {
+        name: option.name.clone(),
+    }
+}
+
+fn argument_item(argument: &OpenCliArgument) -> Item {
+    Item::Argument {
+        name: argument.name.clone(),
+    }
+}
+
+/// The position of an item within the list it was taken from.
+fn index<T>(items: &[T], item: &T) -> usize {
+    items
+        .iter()
+        .position(|other| std::ptr::eq(other, item))
+        .unwrap_or_default()
+}
+
+fn child(path: &[String], name: &str) -> Vec<String> {
+    let mut path = path.to_vec();
+    path.push(name.to_owned());
+    path
+}
+
+/// The severity of a change of the given kind, where it does not depend on the changed item.
+///
+/// Anything which rejects previously accepted input or changes its meaning is breaking: removals,
+/// including options no longer passed on to sub commands, moved arguments, new requirements,
+/// narrower arities, fewer accepted values, including a first list of accepted values, and
+/// different conventions. Hidden items are still accepted, so hiding only affects documentation.
+fn severity(kind: &ChangeKind) -> Severity {
+    match kind {
+        ChangeKind::Added | ChangeKind::AliasAdded { .. } => Severity::NonBreaking,
+        ChangeKind::Removed
+        | ChangeKind::Renamed { .. }
+        | ChangeKind::AliasRemoved { .. }
+        | ChangeKind::Moved { .. }
+        | ChangeKind::GroupOptionsChanged { .. }
+        | ChangeKind::SeparatorChanged { .. } => Severity::Breaking,
+        ChangeKind::RequiredChanged { to, .. } => requirement(*to),
+        ChangeKind::RecursiveChanged { to, .. } => requirement(!to),
+        ChangeKind::ArityChanged { direction, .. } => match direction {
+            ArityDirection::Widened => Severity::NonBreaking,
+            ArityDirection::Narrowed | ArityDirection::Shifted => Severity::Breaking,
+        },
+        ChangeKind::AcceptedValuesChanged { removed, .. } => requirement(!removed.is_empty()),
+        ChangeKind::HiddenChanged { .. } | ChangeKind::DescriptionChanged { .. } => {
+            Severity::Informational
+        }
+    }
+}
+
+/// Breaking if the change imposes a new requirement on existing invocations.
+fn requirement(required: bool) -> Severity {
+    if required {
+        Severity::Breaking
+    } else {
+        Severity::NonBreaking
+    }
+}
@@ -0,0 +1,239 @@
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::include::{has_references, located, parse_value, resolve_includes};
+use crate::{Error, Format, OpenCliDocument, ParseError};
+
+/// The OpenCLI draft schema, transcribed from the specification using only the keywords
+/// understood by [`Checker`].
+static SCHEMA: LazyLock<Value> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../schema/draft.json")).expect("bundled schema is valid")
+});
+
+/// A violation of the OpenCLI schema found in a raw document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaError {
+    /// The JSON pointer to the offending value, e.g. `/commands/2/options/0/arity/minimum`
+    pub pointer: String,
+
+    /// The kind of violation
+    pub kind: SchemaErrorKind,
+}
+
+/// The kinds of violations reported by [`OpenCliDocument::validate_schema`].
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SchemaErrorKind {
+    #[error("Expected {0}")]
+    Type(String),
+    #[error("Missing required field `{0}`")]
+    MissingField(String),
+    #[error("Unknown field `{0}`")]
+    UnknownField(String),
+    #[error("Value {value} is less than the minimum {minimum}")]
+    Minimum { value: f64, minimum: f64 },
+    #[error("Duplicate value {0}")]
+    Duplicate(Value),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "<root>: {}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.pointer, self.kind)
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl OpenCliDocument {
+    /// Check a raw document against the bundled OpenCLI draft schema.
+    ///
+    /// This catches input which deserialization accepts or ignores, e.g. a misspelled optional
+    /// field. In strict mode, fields unknown to the schema are reported as well, except for
+    /// extension fields starting with `x-`. All violations are returned, each with the JSON
+    /// pointer to the offending value.
+    pub fn validate_schema(value: &Value, strict: bool) -> Result<(), Vec<SchemaError>> {
+        let mut checker = Checker {
+            strict,
+            errors: Vec::new(),
+        };
+        checker.check(&SCHEMA, value, "");
+
+        if checker.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(checker.errors)
+        }
+    }
+
+    /// Parse an OpenCLI document from a string after checking it with [`validate_schema`](Self::validate_schema).
+    ///
+    /// The format is detected as when parsing with [`str::parse`].
+    pub fn from_str_checked(content: &str, strict: bool) -> Result<Self, Error> {
+        let (value, format) = Self::parse_detected(content, |content, format| {
+            parse_value(content, format).map(|value| (value, format))
+        })?;
+        Self::from_checked_value(value, content, format, strict)
+    }
+
+    /// Parse an OpenCLI document from a file after checking it with [`validate_schema`](Self::validate_schema).
+    ///
+    /// The format is chosen as by [`from_path`](Self::from_path), and `$ref` includes are
+    /// resolved before checking.
+    pub fn from_path_checked<P: AsRef<Path>>(path: P, strict: bool) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        if has_references(path, &content) {
+            let resolved = resolve_includes(path, &content)?;
+            Self::validate_schema(&resolved.value, strict).map_err(Error::Schema)?;
+            return resolved.deserialize(path, &content);
+        }
+        match Format::from_path(path) {
+            Some(format) => {
+                let value = parse_value(&content, format)?;
+                Self::from_checked_value(value, &content, format, strict)
+            }
+            None => Self::from_str_checked(&content, strict),
+        }
+    }
+
+    /// Parse an OpenCLI document from a reader after checking it with [`validate_schema`](Self::validate_schema).
+    ///
+    /// The format is detected from the content.
+    pub fn from_reader_checked<R: Read>(mut reader: R, strict: bool) -> Result<Self, Error> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        Self::from_str_checked(&content, strict)
+    }
+
+    /// Check a raw document parsed from `content` and deserialize it.
+    fn from_checked_value(
+        value: Value,
+        content: &str,
+        format: Format,
+        strict: bool,
+    ) -> Result<Self, Error> {
+        Self::validate_schema(&value, strict).map_err(Error::Schema)?;
+        serde_path_to_error::deserialize(value).map_err(|error| {
+            located(ParseError::from_json(error), content, format, None, "").into()
+        })
+    }
+}
+
+struct Checker {
+    strict: bool,
+    errors: Vec<SchemaError>,
+}
+
+impl Checker {
+    fn error(&mut self, pointer: &str, kind: SchemaErrorKind) {
+        self.errors.push(SchemaError {
+            pointer: pointer.to_owned(),
+            kind,
+        });
+    }
+
+    fn check(&mut self, schema: &Value, value: &Value, pointer: &str) {
+        let Some(schema) = schema.as_object() else {
+            return;
+        };
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            if let Some(schema) = resolve(reference) {
+                self.check(schema, value, pointer);
+            }
+            return;
+        }
+
+        if let Some(expected) = schema.get("type").and_then(Value::as_str)
+            && !has_type(value, expected)
+        {
+            let expected = match expected {
+                "object" | "array" | "integer" => format!("an {expected}"),
+                _ => format!("a {expected}"),
+            };
+            self.error(pointer, SchemaErrorKind::Type(expected));
+            return;
+        }
+
+        match value {
+            Value::Object(object) => self.object(schema, object, pointer),
+            Value::Array(array) => {
+                if let Some(items) = schema.get("items") {
+                    for (i, item) in array.iter().enumerate() {
+                        self.check(items, item, &format!("{pointer}/{i}"));
+                    }
+                }
+                if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+                    for (i, item) in array.iter().enumerate() {
+                        if array[..i].contains(item) {
+                            let kind = SchemaErrorKind::Duplicate(item.clone());
+                            self.error(&format!("{pointer}/{i}"), kind);
+                        }
+                    }
+                }
+            }
+            Value::Number(number) => {
+                if let (Some(value), Some(minimum)) = (
+                    number.as_f64(),
+                    schema.get("minimum").and_then(Value::as_f64),
+                ) && value < minimum
+                {
+                    self.error(pointer, SchemaErrorKind::Minimum { value, minimum });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn object(&mut self, schema: &Map<String, Value>, object: &Map<String, Value>, pointer: &str) {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for field in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(field) {
+                    self.error(pointer, SchemaErrorKind::MissingField(field.to_owned()));
+                }
+            }
+        }
+        for (key, value) in object {
+            let pointer = format!("{pointer}/{}", escape(key));
+            match properties.and_then(|properties| properties.get(key)) {
+                Some(property) => self.check(property, value, &pointer),
+                None if self.strict && properties.is_some() && !key.starts_with("x-") => {
+                    self.error(&pointer, SchemaErrorKind::UnknownField(key.clone()));
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// Resolve a reference like `#/$defs/Command` within the bundled schema.
+fn resolve(reference: &str) -> Option<&'static Value> {
+    SCHEMA.pointer(reference.strip_prefix('#')?)
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// Escape a key for use in a JSON pointer.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
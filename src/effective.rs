@@ -0,0 +1,51 @@
+use crate::tree::{Node, canonical_path};
+use crate::{OpenCliDocument, OpenCliOption};
+
+/// An option which applies at a command, together with the command defining it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveOption<'a> {
+    /// The option itself
+    pub option: &'a OpenCliOption,
+
+    /// The path of the command defining the option, empty for the root
+    pub source: Vec<&'a str>,
+
+    /// Whether the option is inherited from an ancestor as a recursive option
+    pub inherited: bool,
+}
+
+impl OpenCliDocument {
+    /// All options which apply at the command with the given path of command names or aliases.
+    ///
+    /// These are the recursive options of the ancestors, outermost first, followed by the options
+    /// of the command itself. An option shadows options of the ancestors sharing a name or alias,
+    /// even if it is not recursive itself. An empty path refers to the root. Returns `None` if no
+    /// command exists at the path.
+    pub fn effective_options(&self, path: &[&str]) -> Option<Vec<EffectiveOption<'_>>> {
+        let path = canonical_path(self, path)?;
+        let nodes = Node::collect(self, true);
+        let node = nodes.iter().find(|node| node.path == path)?;
+
+        // The options defined at every level from the root down to the command
+        let mut levels = vec![&self.options];
+        for depth in 1..=path.len() {
+            levels.push(&self.find_command(&path[..depth])?.options);
+        }
+
+        let effective = node
+            .options
+            .iter()
+            .filter_map(|option| {
+                let depth = levels.iter().position(|options| {
+                    options.iter().any(|defined| std::ptr::eq(defined, *option))
+                })?;
+                Some(EffectiveOption {
+                    option,
+                    source: path[..depth].to_vec(),
+                    inherited: depth < path.len(),
+                })
+            })
+            .collect();
+        Some(effective)
+    }
+}
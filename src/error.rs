@@ -1,12 +1,163 @@
-use std::io;
+use std::{fmt, io, path::PathBuf};
 use thiserror::Error;
 
+use crate::{Format, SchemaError};
+
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("Parsing error")]
-    Parse(#[from] serde_json::Error),
+    #[error("Parsing error: {error}")]
+    Parse {
+        /// The error of the detected format
+        error: Box<ParseError>,
+        /// The error of the other format, if parsing was attempted with both
+        fallback: Option<Box<ParseError>>,
+    },
+    #[error("{format} serialization error")]
+    Serialize {
+        format: Format,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
     #[error("Filesystem access error")]
     Io(#[from] io::Error),
+    #[error("Error in included file `{}`: {source}", path.display())]
+    Include {
+        /// The included file, relative to the current directory
+        path: PathBuf,
+        #[source]
+        source: Box<Error>,
+    },
+    #[error("Include cycle: {}", cycle(.0))]
+    IncludeCycle(Vec<PathBuf>),
+    #[error("Invalid `$ref` at `{0}`")]
+    InvalidReference(String),
+    #[error("Document does not match the OpenCLI schema{}", schema_errors(.0))]
+    Schema(Vec<SchemaError>),
     #[error("Other error")]
     Other(&'static str),
 }
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse {
+            error: Box::new(error),
+            fallback: None,
+        }
+    }
+}
+
+/// Describes why and where a document could not be parsed in a specific format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The format the document was parsed as
+    pub format: Format,
+
+    /// The path to the offending field, e.g. `commands[3].options[1].arity.minimum`
+    pub path: Option<String>,
+
+    /// The line of the error, starting at 1
+    pub line: Option<usize>,
+
+    /// The column of the error, starting at 1
+    pub column: Option<usize>,
+
+    /// The error message without location information
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn from_yaml(error: serde_path_to_error::Error<serde_yaml::Error>) -> Self {
+        let path = field_path(error.path());
+        let error = error.into_inner();
+        let location = error.location();
+        let line = location.as_ref().map(|location| location.line());
+        let column = location.as_ref().map(|location| location.column());
+        let mut message = error.to_string();
+        if let Some(path) = &path {
+            message = strip_prefix(message, &format!("{path}: "));
+        }
+        if let (Some(line), Some(column)) = (line, column) {
+            message = strip_suffix(message, &format!(" at line {line} column {column}"));
+        }
+
+        ParseError {
+            format: Format::Yaml,
+            path,
+            line,
+            column,
+            message,
+        }
+    }
+
+    pub(crate) fn from_json(error: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        let path = field_path(error.path());
+        let error = error.into_inner();
+        let (line, column) = match error.line() {
+            0 => (None, None),
+            line => (Some(line), Some(error.column())),
+        };
+        let mut message = error.to_string();
+        if let (Some(line), Some(column)) = (line, column) {
+            message = strip_suffix(message, &format!(" at line {line} column {column}"));
+        }
+
+        ParseError {
+            format: Format::Json,
+            path,
+            line,
+            column,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {}", self.format)?;
+        if let Some(path) = &self.path {
+            write!(f, " at `{path}`")?;
+        }
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, " (line {line}, column {column})")?,
+            (Some(line), None) => write!(f, " (line {line})")?,
+            _ => {}
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn cycle(files: &[PathBuf]) -> String {
+    let files: Vec<_> = files
+        .iter()
+        .map(|file| file.display().to_string())
+        .collect();
+    files.join(" -> ")
+}
+
+fn schema_errors(errors: &[SchemaError]) -> String {
+    match errors {
+        [] => String::new(),
+        [error] => format!(", 1 error: {error}"),
+        [error, ..] => format!(", {} errors, the first: {error}", errors.len()),
+    }
+}
+
+fn field_path(path: &serde_path_to_error::Path) -> Option<String> {
+    path.iter().next().map(|_| path.to_string())
+}
+
+fn strip_prefix(message: String, prefix: &str) -> String {
+    match message.strip_prefix(prefix) {
+        Some(stripped) => stripped.to_owned(),
+        None => message,
+    }
+}
+
+fn strip_suffix(message: String, suffix: &str) -> String {
+    match message.strip_suffix(suffix) {
+        Some(stripped) => stripped.to_owned(),
+        None => message,
+    }
+}
@@ -0,0 +1,43 @@
+use std::{fmt, path::Path};
+
+/// The serialization format of an OpenCLI document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// YAML
+    Yaml,
+    /// JSON
+    Json,
+}
+
+impl Format {
+    /// Determine the format from the extension of a file path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?;
+        if extension.eq_ignore_ascii_case("yaml") || extension.eq_ignore_ascii_case("yml") {
+            Some(Format::Yaml)
+        } else if extension.eq_ignore_ascii_case("json") {
+            Some(Format::Json)
+        } else {
+            None
+        }
+    }
+
+    /// Guess the format of a document from its content.
+    ///
+    /// Documents starting with `{` or `[` are considered JSON, everything else YAML.
+    pub fn detect(content: &str) -> Self {
+        match content.trim_start().chars().next() {
+            Some('{' | '[') => Format::Json,
+            _ => Format::Yaml,
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Yaml => f.write_str("YAML"),
+            Format::Json => f.write_str("JSON"),
+        }
+    }
+}
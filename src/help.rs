@@ -0,0 +1,292 @@
+use std::fmt::Write;
+
+use crate::tree::{Node, canonical_path, value_range};
+use crate::usage::node_usage;
+use crate::{OpenCliArgument, OpenCliDocument, OpenCliOption};
+
+/// The width used when the terminal width is unknown.
+const DEFAULT_WIDTH: usize = 80;
+
+/// The maximum width of the name column before descriptions move to their own line.
+const MAX_NAME_WIDTH: usize = 30;
+
+impl OpenCliDocument {
+    /// Render the help screen of the command at the given path, wrapped to the terminal width.
+    ///
+    /// The path consists of command names or aliases, an empty path renders the help of the
+    /// root command. The terminal width is taken from the `COLUMNS` environment variable,
+    /// falling back to 80 columns. Returns `None` if no command exists at the path.
+    pub fn help(&self, path: &[&str]) -> Option<String> {
+        let width = std::env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.trim().parse().ok())
+            .filter(|&columns| columns > 0)
+            .unwrap_or(DEFAULT_WIDTH);
+        self.help_with_width(path, width)
+    }
+
+    /// Render the help screen of the command at the given path, wrapped to the given width.
+    pub fn help_with_width(&self, path: &[&str], width: usize) -> Option<String> {
+        let path = canonical_path(self, path)?;
+        let nodes = Node::collect(self, true);
+        let node = nodes.iter().find(|node| node.path == path)?;
+        Some(render(self, node, width))
+    }
+}
+
+/// A titled list of entries in the help screen.
+struct Section {
+    title: String,
+    entries: Vec<(String, String)>,
+}
+
+fn render(document: &OpenCliDocument, node: &Node, width: usize) -> String {
+    let mut help = String::new();
+
+    let (description, aliases, examples) = match node.command {
+        Some(command) => (
+            command.description.as_deref(),
+            command.aliases.as_slice(),
+            command.examples.as_slice(),
+        ),
+        None => (
+            document
+                .info
+                .description
+                .as_deref()
+                .or(document.info.summary.as_deref()),
+            &[][..],
+            document.examples.as_slice(),
+        ),
+    };
+
+    if let Some(description) = description {
+        for paragraph in description.trim().split("\n\n") {
+            for line in wrap(&paragraph.replace('\n', " "), width) {
+                writeln!(help, "{line}").unwrap();
+            }
+            writeln!(help).unwrap();
+        }
+    }
+
+    writeln!(help, "Usage: {}", node_usage(document, node)).unwrap();
+    if !aliases.is_empty() {
+        writeln!(help).unwrap();
+        writeln!(help, "Aliases: {}", aliases.join(", ")).unwrap();
+    }
+
+    // All sections share the same name column
+    let sections = sections(node);
+    let name_width = sections
+        .iter()
+        .flat_map(|section| &section.entries)
+        .map(|(name, _)| name.chars().count())
+        .filter(|&width| width <= MAX_NAME_WIDTH)
+        .max()
+        .unwrap_or(0);
+    for section in sections {
+        writeln!(help).unwrap();
+        writeln!(help, "{}:", section.title).unwrap();
+        write_entries(&mut help, &section.entries, name_width, width);
+    }
+
+    if !examples.is_empty() {
+        writeln!(help).unwrap();
+        writeln!(help, "Examples:").unwrap();
+        for example in examples {
+            for line in example.lines() {
+                writeln!(help, "  {line}").unwrap();
+            }
+        }
+    }
+
+    help
+}
+
+/// Group the visible commands, arguments and options into sections.
+///
+/// Ungrouped arguments and options get their own section, grouped ones are collected in
+/// a section per group, in order of appearance.
+fn sections(node: &Node) -> Vec<Section> {
+    let mut commands = Section {
+        title: "Commands".to_owned(),
+        entries: Vec::new(),
+    };
+    let mut arguments = Section {
+        title: "Arguments".to_owned(),
+        entries: Vec::new(),
+    };
+    let mut options = Section {
+        title: "Options".to_owned(),
+        entries: Vec::new(),
+    };
+    let mut groups: Vec<Section> = Vec::new();
+
+    let mut add = |group: &Option<String>, default: &mut Section, entry: (String, String)| {
+        let Some(group) = group else {
+            default.entries.push(entry);
+            return;
+        };
+        match groups.iter_mut().find(|section| &section.title == group) {
+            Some(section) => section.entries.push(entry),
+            None => groups.push(Section {
+                title: group.clone(),
+                entries: vec![entry],
+            }),
+        }
+    };
+
+    for command in &node.commands {
+        if command.hidden.unwrap_or(false) {
+            continue;
+        }
+        let names: Vec<&str> = std::iter::once(command.name.as_str())
+            .chain(command.aliases.iter().map(String::as_str))
+            .collect();
+        commands.entries.push((
+            names.join(", "),
+            command.description.clone().unwrap_or_default(),
+        ));
+    }
+    for argument in &node.arguments {
+        if argument.hidden.unwrap_or(false) {
+            continue;
+        }
+        add(
+            &argument.group,
+            &mut arguments,
+            (
+                format!("<{}>", argument.name),
+                argument_description(argument),
+            ),
+        );
+    }
+    for option in &node.options {
+        if option.hidden.unwrap_or(false) {
+            continue;
+        }
+        add(
+            &option.group,
+            &mut options,
+            (option_names(option), option_description(option)),
+        );
+    }
+
+    [commands, arguments, options]
+        .into_iter()
+        .chain(groups)
+        .filter(|section| !section.entries.is_empty())
+        .collect()
+}
+
+fn option_names(option: &OpenCliOption) -> String {
+    let mut names = std::iter::once(option.name.as_str())
+        .chain(option.aliases.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(", ");
+    for argument in &option.arguments {
+        match value_range(argument) {
+            (_, Some(0)) => {}
+            (0, _) => write!(names, " [<{}>]", argument.name).unwrap(),
+            (_, Some(1)) => write!(names, " <{}>", argument.name).unwrap(),
+            _ => write!(names, " <{}>...", argument.name).unwrap(),
+        }
+    }
+    names
+}
+
+fn option_description(option: &OpenCliOption) -> String {
+    let mut description = option.description.clone().unwrap_or_default();
+    let accepted_values: Vec<&str> = option
+        .arguments
+        .iter()
+        .flat_map(|argument| argument.accepted_values.iter().map(String::as_str))
+        .collect();
+    annotate(
+        &mut description,
+        option.required.unwrap_or(false),
+        &accepted_values,
+    );
+    description
+}
+
+fn argument_description(argument: &OpenCliArgument) -> String {
+    let mut description = argument.description.clone().unwrap_or_default();
+    let accepted_values: Vec<&str> = argument
+        .accepted_values
+        .iter()
+        .map(String::as_str)
+        .collect();
+    annotate(
+        &mut description,
+        argument.required.unwrap_or(false),
+        &accepted_values,
+    );
+    description
+}
+
+fn annotate(description: &mut String, required: bool, accepted_values: &[&str]) {
+    let mut notes = Vec::new();
+    if required {
+        notes.push("[required]".to_owned());
+    }
+    if !accepted_values.is_empty() {
+        notes.push(format!("[possible values: {}]", accepted_values.join(", ")));
+    }
+    for note in notes {
+        if !description.is_empty() {
+            description.push(' ');
+        }
+        description.push_str(&note);
+    }
+}
+
+fn write_entries(help: &mut String, entries: &[(String, String)], name_width: usize, width: usize) {
+    const INDENT: usize = 2;
+    const GAP: usize = 2;
+
+    let column = INDENT + name_width + GAP;
+    let description_width = width.saturating_sub(column).max(20);
+
+    for (name, description) in entries {
+        let name_length = name.chars().count();
+        let lines = wrap(description, description_width);
+        if lines.is_empty() {
+            writeln!(help, "{:INDENT$}{name}", "").unwrap();
+            continue;
+        }
+        if name_length > name_width {
+            writeln!(help, "{:INDENT$}{name}", "").unwrap();
+            for line in lines {
+                writeln!(help, "{:column$}{line}", "").unwrap();
+            }
+        } else {
+            for (i, line) in lines.into_iter().enumerate() {
+                if i == 0 {
+                    writeln!(help, "{:INDENT$}{name:name_width$}{:GAP$}{line}", "", "").unwrap();
+                } else {
+                    writeln!(help, "{:column$}{line}", "").unwrap();
+                }
+            }
+        }
+    }
+}
+
+/// Wrap text at word boundaries to the given width.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
@@ -0,0 +1,444 @@
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+
+use crate::{Error, Format, OpenCliDocument, ParseError};
+
+/// The key of an object which is replaced by the content of another file.
+const REFERENCE: &str = "$ref";
+
+impl OpenCliDocument {
+    /// Resolve the `$ref` includes of the document at the given path and serialize the result
+    /// as a single document in the given format, e.g. for publishing.
+    pub fn bundle<P: AsRef<Path>>(path: P, format: Format) -> Result<String, Error> {
+        let document = Self::from_path(path)?;
+        match format {
+            Format::Yaml => document.to_yaml_string(),
+            Format::Json => document.to_json_string(true),
+        }
+    }
+
+    /// Parse a document split across files with `$ref` includes, see [`OpenCliDocument::from_path`].
+    pub(crate) fn from_path_with_includes(path: &Path, content: &str) -> Result<Self, Error> {
+        resolve_includes(path, content)?.deserialize(path, content)
+    }
+}
+
+/// A raw document with its `$ref` includes resolved.
+pub(crate) struct Resolved {
+    pub(crate) value: Value,
+
+    /// Every include, in the order of resolution
+    sources: Vec<Source>,
+}
+
+/// Parse a document into a raw value with its `$ref` includes resolved.
+pub(crate) fn resolve_includes(path: &Path, content: &str) -> Result<Resolved, Error> {
+    let mut resolver = Resolver::default();
+    let value = resolver.load(path, content, None, "", None)?;
+    Ok(Resolved {
+        value,
+        sources: resolver.sources,
+    })
+}
+
+impl Resolved {
+    /// Deserialize the document read from `path`, reporting errors in the file they occur in.
+    pub(crate) fn deserialize(self, path: &Path, content: &str) -> Result<OpenCliDocument, Error> {
+        let sources = self.sources;
+        serde_path_to_error::deserialize(self.value).map_err(|error| {
+            let error = ParseError::from_json(error);
+            // Attribute the error to the innermost included file containing the offending field
+            let field = error.path.as_deref().unwrap_or_default();
+            let source = sources
+                .iter()
+                .filter(|source| contains(&source.location, field))
+                .max_by_key(|source| source.location.len());
+            match source {
+                Some(source) => {
+                    let content = fs::read_to_string(&source.path).unwrap_or_default();
+                    let format = format_of(&source.path, &content);
+                    let error = located(
+                        error,
+                        &content,
+                        format,
+                        source.pointer.as_deref(),
+                        &source.location,
+                    );
+                    Error::Include {
+                        path: source.path.clone(),
+                        source: Box::new(error.into()),
+                    }
+                }
+                None => located(error, content, format_of(path, content), None, "").into(),
+            }
+        })
+    }
+}
+
+/// Add the format and location to an error found deserializing a value parsed from `content`.
+///
+/// The value has no source locations, so the offending field is searched in the content instead.
+/// It is found at `location` in the deserialized value, and at `pointer` in the content.
+pub(crate) fn located(
+    mut error: ParseError,
+    content: &str,
+    format: Format,
+    pointer: Option<&str>,
+    location: &str,
+) -> ParseError {
+    let field = error.path.as_deref().unwrap_or_default();
+    let mut segments = pointer.map(pointer_segments).unwrap_or_default();
+    segments.extend(path_segments(
+        field.get(location.len()..).unwrap_or_default(),
+    ));
+    error.format = format;
+    (error.line, error.column) = match locate(content, format, &segments) {
+        Some((line, column)) => (Some(line), Some(column)),
+        None => (None, None),
+    };
+    error
+}
+
+/// The kinds of entries which can be included with a `$ref`.
+#[derive(Debug, Clone, Copy)]
+enum Entry {
+    Command,
+    Option,
+    Argument,
+    ExitCode,
+}
+
+impl Entry {
+    /// The fields holding lists of nested entries, the document root having those of a command.
+    fn children(self) -> &'static [(&'static str, Entry)] {
+        match self {
+            Entry::Command => &[
+                ("options", Entry::Option),
+                ("arguments", Entry::Argument),
+                ("commands", Entry::Command),
+                ("exitCodes", Entry::ExitCode),
+            ],
+            Entry::Option => &[("arguments", Entry::Argument)],
+            Entry::Argument | Entry::ExitCode => &[],
+        }
+    }
+}
+
+/// Whether a document contains a `$ref` where a command, option, argument or exit code is
+/// expected. Documents which cannot be parsed have none.
+pub(crate) fn has_references(path: &Path, content: &str) -> bool {
+    fn nested(value: &Value, entry: Entry) -> bool {
+        entry
+            .children()
+            .iter()
+            .any(|(field, entry)| match value.get(field) {
+                Some(Value::Array(items)) => items
+                    .iter()
+                    .any(|item| item.get(REFERENCE).is_some() || nested(item, *entry)),
+                _ => false,
+            })
+    }
+
+    // Skip parsing the document twice if it cannot contain any
+    content.contains(REFERENCE)
+        && parse_value(content, format_of(path, content))
+            .is_ok_and(|document| nested(&document, Entry::Command))
+}
+
+#[derive(Default)]
+struct Resolver {
+    /// The canonical paths of the files being resolved, outermost first
+    stack: Vec<PathBuf>,
+
+    /// Every include, in the order of resolution
+    sources: Vec<Source>,
+}
+
+/// A file included in the resolved document.
+struct Source {
+    /// The location of the include in the resolved document, e.g. `commands[1]`
+    location: String,
+
+    /// The included file
+    path: PathBuf,
+
+    /// The JSON pointer selecting the included part of the file
+    pointer: Option<String>,
+}
+
+impl Resolver {
+    /// Parse the content of a file, or the part of it selected by a JSON pointer, and resolve its
+    /// includes, `location` being its place in the resolved document.
+    ///
+    /// The content is an entry of the given kind, or the document itself if there is none.
+    fn load(
+        &mut self,
+        path: &Path,
+        content: &str,
+        pointer: Option<&str>,
+        location: &str,
+        entry: Option<Entry>,
+    ) -> Result<Value, Error> {
+        let canonical = fs::canonicalize(path)?;
+        if let Some(start) = self.stack.iter().position(|file| *file == canonical) {
+            let mut cycle = self.stack[start..].to_vec();
+            cycle.push(canonical);
+            return Err(Error::IncludeCycle(cycle));
+        }
+
+        let mut value = parse_value(content, format_of(path, content))?;
+        if let Some(pointer) = pointer {
+            value = value
+                .pointer(pointer)
+                .cloned()
+                .ok_or_else(|| Error::InvalidReference(location.to_owned()))?;
+        }
+        self.stack.push(canonical);
+        let directory = path.parent().unwrap_or(Path::new(""));
+        let result = match entry {
+            Some(entry) => self.entry(&mut value, entry, directory, location),
+            None => self.children(&mut value, Entry::Command, directory, location),
+        };
+        self.stack.pop();
+        result.map(|()| value)
+    }
+
+    /// Resolve an entry, which may be a reference itself.
+    fn entry(
+        &mut self,
+        value: &mut Value,
+        entry: Entry,
+        directory: &Path,
+        location: &str,
+    ) -> Result<(), Error> {
+        let Value::Object(object) = value else {
+            return Ok(());
+        };
+        let Some(reference) = object.remove(REFERENCE) else {
+            return self.children(value, entry, directory, location);
+        };
+        let Value::String(reference) = reference else {
+            return Err(Error::InvalidReference(location.to_owned()));
+        };
+        let overrides = std::mem::take(object);
+        let mut included = self.include(directory, &reference, location, entry)?;
+        // Fields next to the reference override the included ones
+        if !overrides.is_empty() {
+            let mut overrides = Value::Object(overrides);
+            self.children(&mut overrides, entry, directory, location)?;
+            let (Value::Object(fields), Value::Object(overrides)) = (&mut included, &mut overrides)
+            else {
+                return Err(Error::InvalidReference(location.to_owned()));
+            };
+            fields.append(overrides);
+        }
+        *value = included;
+        Ok(())
+    }
+
+    /// Resolve the nested entries of an entry.
+    fn children(
+        &mut self,
+        value: &mut Value,
+        entry: Entry,
+        directory: &Path,
+        location: &str,
+    ) -> Result<(), Error> {
+        for (field, entry) in entry.children() {
+            let Some(Value::Array(items)) = value.get_mut(field) else {
+                continue;
+            };
+            for (i, item) in items.iter_mut().enumerate() {
+                let location = if location.is_empty() {
+                    format!("{field}[{i}]")
+                } else {
+                    format!("{location}.{field}[{i}]")
+                };
+                self.entry(item, *entry, directory, &location)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Load a reference like `commands/deploy.yaml`, optionally followed by a JSON pointer into
+    /// the file like `#/commands/0`.
+    fn include(
+        &mut self,
+        directory: &Path,
+        reference: &str,
+        location: &str,
+        entry: Entry,
+    ) -> Result<Value, Error> {
+        let (file, pointer) = match reference.split_once('#') {
+            Some((file, pointer)) => (file, Some(pointer).filter(|pointer| !pointer.is_empty())),
+            None => (reference, None),
+        };
+        if file.is_empty() {
+            return Err(Error::InvalidReference(location.to_owned()));
+        }
+
+        let path: PathBuf = directory
+            .join(file)
+            .components()
+            .filter(|component| *component != Component::CurDir)
+            .collect();
+        let wrap = |error: Error| match error {
+            Error::Include { .. } | Error::IncludeCycle(_) => error,
+            error => Error::Include {
+                path: path.clone(),
+                source: Box::new(error),
+            },
+        };
+        let content = fs::read_to_string(&path).map_err(|error| wrap(error.into()))?;
+        self.sources.push(Source {
+            location: location.to_owned(),
+            path: path.clone(),
+            pointer: pointer.map(str::to_owned),
+        });
+        self.load(&path, &content, pointer, location, Some(entry))
+            .map_err(wrap)
+    }
+}
+
+/// The format of a file, chosen by its extension or detected from the content.
+pub(crate) fn format_of(path: &Path, content: &str) -> Format {
+    Format::from_path(path).unwrap_or_else(|| Format::detect(content))
+}
+
+/// Parse a document into a raw value, without interpreting it.
+pub(crate) fn parse_value(content: &str, format: Format) -> Result<Value, ParseError> {
+    match format {
+        Format::Yaml => {
+            serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(content))
+                .map_err(ParseError::from_yaml)
+        }
+        Format::Json => {
+            serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(content))
+                .map_err(ParseError::from_json)
+        }
+    }
+}
+
+/// Whether the field at `path` lies within the field at `prefix`.
+fn contains(prefix: &str, path: &str) -> bool {
+    match path.strip_prefix(prefix) {
+        Some(rest) => prefix.is_empty() || rest.is_empty() || rest.starts_with(['.', '[']),
+        None => false,
+    }
+}
+
+/// The segments of a field path like `commands[3].options[1].arity`, indices included.
+fn path_segments(path: &str) -> Vec<String> {
+    path.split(['.', '['])
+        .map(|segment| segment.trim_end_matches(']'))
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// The segments of a JSON pointer like `/options/0`.
+fn pointer_segments(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+/// Find the line and column of the value at the given path in a file.
+///
+/// The text is walked up to the value, which is then rejected on purpose to obtain the location
+/// of the error from the deserializer.
+fn locate(content: &str, format: Format, path: &[String]) -> Option<(usize, usize)> {
+    let location = match format {
+        Format::Yaml => {
+            let error = Locate(path)
+                .deserialize(serde_yaml::Deserializer::from_str(content))
+                .err()?;
+            let location = error.location()?;
+            error
+                .to_string()
+                .contains(LOCATED)
+                .then(|| (location.line(), location.column()))
+        }
+        Format::Json => {
+            let error = Locate(path)
+                .deserialize(&mut serde_json::Deserializer::from_str(content))
+                .err()?;
+            error
+                .to_string()
+                .contains(LOCATED)
+                .then(|| (error.line(), error.column()))
+        }
+    };
+    location.filter(|(line, _)| *line > 0)
+}
+
+/// The expectation reported when reaching the value searched by [`locate`].
+const LOCATED: &str = "the located value";
+
+/// Walks a document along a path of map keys and sequence indices.
+struct Locate<'p>(&'p [String]);
+
+impl<'de> DeserializeSeed<'de> for Locate<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        if self.0.is_empty() {
+            deserializer.deserialize_any(Located)
+        } else {
+            deserializer.deserialize_any(self)
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for Locate<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map or a sequence")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let (segment, rest) = self.0.split_first().expect("path is not empty");
+        while let Some(key) = map.next_key::<String>()? {
+            if key == *segment {
+                map.next_value_seed(Locate(rest))?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let (segment, rest) = self.0.split_first().expect("path is not empty");
+        let index = segment.parse::<usize>().ok();
+        for i in 0.. {
+            let element = if Some(i) == index {
+                seq.next_element_seed(Locate(rest))?
+            } else {
+                seq.next_element::<IgnoredAny>()?.map(drop)
+            };
+            if element.is_none() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rejects any value, see [`locate`].
+struct Located;
+
+impl<'de> Visitor<'de> for Located {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(LOCATED)
+    }
+}
@@ -7,15 +7,62 @@
 //!
 //! let opencli = OpenCliDocument::from_path("path/to/opencli.yaml").unwrap();
 //! ```
+//!
+//! Fields unknown to this crate are kept in the `extensions` of the enclosing struct, so a
+//! document can be read and written without losing them. Only `x-` fields are extensions in the
+//! specification though: [`OpenCliDocument::validate_schema`] rejects all other unknown fields in
+//! strict mode.
 
 use serde::{Deserialize, Serialize};
-use std::{fs, path::Path};
-
+use std::{
+    fs,
+    io::{Read, Write},
+    path::Path,
+};
+
+pub mod completion;
+pub mod diff;
+pub mod man;
+pub mod markdown;
+pub mod visit;
+
+mod args;
+mod builder;
+#[cfg(feature = "clap")]
+mod clap;
+mod draft;
+mod effective;
 mod error;
-pub use error::Error;
+mod format;
+mod help;
+mod include;
+mod lookup;
+mod merge;
+#[cfg(feature = "schemars")]
+mod schema;
+mod tree;
+mod usage;
+mod validate;
+
+pub use args::{ArgMatches, ArgsError, ArgsErrorKind, ArgumentMatch, OptionMatch};
+pub use builder::OpenCliDocumentBuilder;
+#[cfg(feature = "clap")]
+pub use clap::{ClapIncompatibility, ClapIncompatibilityKind};
+pub use diff::diff;
+pub use draft::{SchemaError, SchemaErrorKind};
+pub use effective::EffectiveOption;
+pub use error::{Error, ParseError};
+pub use format::Format;
+pub use lookup::{CommandIter, CommandRef};
+pub use merge::{MergeConflict, MergeConflictKind};
+pub use validate::{ValidationError, ValidationErrorKind};
+
+/// The version of the OpenCLI specification implemented by this crate.
+pub const OPENCLI_VERSION: &str = "0.1";
 
 /// This is the root object of the OpenCLI Description.
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliDocument {
     /// The OpenCLI version number
@@ -55,9 +102,14 @@ pub struct OpenCliDocument {
     /// Custom metadata
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub metadata: Vec<OpenCliMetadata>,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliInfo {
     /// The application title
@@ -81,9 +133,14 @@ pub struct OpenCliInfo {
 
     /// The application version
     pub version: String,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliConventions {
     /// Whether or not grouping of short options are allowed
@@ -93,9 +150,14 @@ pub struct OpenCliConventions {
     /// The option argument separator
     #[serde(skip_serializing_if = "Option::is_none")]
     pub option_argument_separator: Option<String>,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliContact {
     /// The identifying name of the contact person/organization
@@ -109,9 +171,14 @@ pub struct OpenCliContact {
     /// The email address of the contact person/organization. This MUST be in the form of an email address
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliLicense {
     /// The license name
@@ -121,9 +188,14 @@ pub struct OpenCliLicense {
     /// The [SPDX](https://spdx.org/licenses/) license identifier
     #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier: Option<String>,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliCommand {
     /// The command name
@@ -168,9 +240,14 @@ pub struct OpenCliCommand {
     /// Custom metadata
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub metadata: Vec<OpenCliMetadata>,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliArgument {
     /// The argument name
@@ -203,9 +280,14 @@ pub struct OpenCliArgument {
     /// Custom metadata
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub metadata: Vec<OpenCliMetadata>,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliOption {
     /// The option name
@@ -242,9 +324,14 @@ pub struct OpenCliOption {
     /// Custom metadata
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub metadata: Vec<OpenCliMetadata>,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliArity {
     /// The minimum number of values allowed
@@ -254,9 +341,14 @@ pub struct OpenCliArity {
     /// The maximum number of values allowed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub maximum: Option<i32>,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliExitCode {
     /// The exit code
@@ -265,9 +357,14 @@ pub struct OpenCliExitCode {
     /// The exit code description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCliMetadata {
     /// The metadata name
@@ -276,30 +373,154 @@ pub struct OpenCliMetadata {
     /// The metadata value
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<serde_json::Value>,
+
+    /// Unknown and `x-` fields, preserved on round-trip
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
 impl OpenCliDocument {
     /// Parse an OpenCLI document from a file.
+    ///
+    /// The format is chosen by the file extension (`.yaml`, `.yml` or `.json`),
+    /// otherwise it is detected from the content.
+    ///
+    /// Any command, option, argument or exit code of the form `{ $ref: "commands/deploy.yaml" }`
+    /// is replaced by the content of the referenced file, resolved relative to the including file.
+    /// A `$ref` anywhere else, e.g. in a metadata value, is kept as is. A reference may select a
+    /// part of the file with a JSON pointer like `shared.yaml#/options/0`, and other fields next to
+    /// the `$ref` override the included ones. Included files may include further files, but not
+    /// themselves.
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
         let path = path.as_ref();
         let content = fs::read_to_string(path)?;
-        Self::from_str(&content)
+        Self::from_file_content(path, &content)
+    }
+
+    /// Parse the content of the file at the given path, see [`OpenCliDocument::from_path`].
+    fn from_file_content(path: &Path, content: &str) -> Result<Self, Error> {
+        if include::has_references(path, content) {
+            return Self::from_path_with_includes(path, content);
+        }
+        match Format::from_path(path) {
+            Some(Format::Yaml) => Self::from_yaml_str(content),
+            Some(Format::Json) => Self::from_json_str(content),
+            None => content.parse(),
+        }
+    }
+
+    /// Parse an OpenCLI document from a reader, detecting the format from the content.
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, Error> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        content.parse()
     }
 
     /// Parse an OpenCLI document from a byte slice.
     pub fn from_slice(content: &[u8]) -> Result<Self, Error> {
         let content = str::from_utf8(content).map_err(|_| Error::Other("utf8"))?;
-        Self::from_str(content)
+        content.parse()
     }
 
-    /// Parse an OpenCLI document from a string.
-    pub fn from_str(content: &str) -> Result<Self, Error> {
-        match serde_yaml::from_str(content) {
+    /// Parse content with `parse` in the detected format, falling back to the other format.
+    pub(crate) fn parse_detected<T>(
+        content: &str,
+        parse: impl Fn(&str, Format) -> Result<T, ParseError>,
+    ) -> Result<T, Error> {
+        let detected = Format::detect(content);
+        let error = match parse(content, detected) {
+            Ok(data) => return Ok(data),
+            Err(error) => error,
+        };
+
+        let other = match detected {
+            Format::Yaml => Format::Json,
+            Format::Json => Format::Yaml,
+        };
+        match parse(content, other) {
             Ok(data) => Ok(data),
-            Err(_) => {
-                let json_data = serde_json::from_str(content)?;
-                Ok(json_data)
+            Err(fallback) => Err(Error::Parse {
+                error: Box::new(error),
+                fallback: Some(Box::new(fallback)),
+            }),
+        }
+    }
+
+    /// Parse an OpenCLI document from a YAML string.
+    pub fn from_yaml_str(content: &str) -> Result<Self, Error> {
+        Self::parse(content, Format::Yaml).map_err(Error::from)
+    }
+
+    /// Parse an OpenCLI document from a JSON string.
+    pub fn from_json_str(content: &str) -> Result<Self, Error> {
+        Self::parse(content, Format::Json).map_err(Error::from)
+    }
+
+    fn parse(content: &str, format: Format) -> Result<Self, ParseError> {
+        match format {
+            Format::Yaml => {
+                serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(content))
+                    .map_err(ParseError::from_yaml)
+            }
+            Format::Json => {
+                serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(content))
+                    .map_err(ParseError::from_json)
             }
         }
     }
+
+    /// Write the OpenCLI document to a file.
+    ///
+    /// The format is chosen by the file extension, defaulting to YAML.
+    pub fn to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let format = Format::from_path(path).unwrap_or(Format::Yaml);
+        let file = fs::File::create(path)?;
+        self.to_writer(file, format)
+    }
+
+    /// Write the OpenCLI document to a writer in the given format.
+    ///
+    /// JSON output is pretty-printed.
+    pub fn to_writer<W: Write>(&self, mut writer: W, format: Format) -> Result<(), Error> {
+        let content = match format {
+            Format::Yaml => self.to_yaml_string()?,
+            Format::Json => self.to_json_string(true)? + "\n",
+        };
+        writer.write_all(content.as_bytes())?;
+        Ok(())
+    }
+
+    /// Serialize the OpenCLI document to a YAML string.
+    pub fn to_yaml_string(&self) -> Result<String, Error> {
+        serde_yaml::to_string(self).map_err(|error| Error::Serialize {
+            format: Format::Yaml,
+            source: Box::new(error),
+        })
+    }
+
+    /// Serialize the OpenCLI document to a JSON string, optionally pretty-printed.
+    pub fn to_json_string(&self, pretty: bool) -> Result<String, Error> {
+        let result = if pretty {
+            serde_json::to_string_pretty(self)
+        } else {
+            serde_json::to_string(self)
+        };
+        result.map_err(|error| Error::Serialize {
+            format: Format::Json,
+            source: Box::new(error),
+        })
+    }
+}
+
+impl std::str::FromStr for OpenCliDocument {
+    type Err = Error;
+
+    /// Parse an OpenCLI document from a string.
+    ///
+    /// The format is detected from the content. If parsing in the detected format fails,
+    /// the other format is tried before giving up.
+    fn from_str(content: &str) -> Result<Self, Error> {
+        Self::parse_detected(content, Self::parse)
+    }
 }
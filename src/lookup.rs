@@ -0,0 +1,139 @@
+use crate::{OpenCliArgument, OpenCliCommand, OpenCliDocument, OpenCliOption};
+
+/// A command of a document together with its location in the command tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandRef<'a> {
+    /// The command names leading to the command, including its own name
+    pub path: Vec<&'a str>,
+
+    /// The command itself
+    pub command: &'a OpenCliCommand,
+
+    /// The ancestors of the command, outermost first
+    pub parents: Vec<&'a OpenCliCommand>,
+}
+
+impl CommandRef<'_> {
+    /// The nesting depth of the command, 1 for commands directly below the root.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+}
+
+/// An iterator over all commands of a document, created by [`OpenCliDocument::iter_commands`].
+#[derive(Debug, Clone)]
+pub struct CommandIter<'a> {
+    stack: Vec<CommandRef<'a>>,
+}
+
+impl<'a> CommandIter<'a> {
+    fn new(commands: &'a [OpenCliCommand]) -> Self {
+        let mut iter = CommandIter { stack: Vec::new() };
+        iter.push_children(commands, &[], &[]);
+        iter
+    }
+
+    fn push_children(
+        &mut self,
+        commands: &'a [OpenCliCommand],
+        path: &[&'a str],
+        parents: &[&'a OpenCliCommand],
+    ) {
+        // Reversed, so the first command is visited first
+        for command in commands.iter().rev() {
+            let mut path = path.to_vec();
+            path.push(&command.name);
+            self.stack.push(CommandRef {
+                path,
+                command,
+                parents: parents.to_vec(),
+            });
+        }
+    }
+}
+
+impl<'a> Iterator for CommandIter<'a> {
+    type Item = CommandRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.stack.pop()?;
+        let mut parents = next.parents.clone();
+        parents.push(next.command);
+        self.push_children(&next.command.commands, &next.path, &parents);
+        Some(next)
+    }
+}
+
+impl OpenCliDocument {
+    /// Find the command at the given path of command names or aliases, e.g. `&["remote", "add"]`.
+    ///
+    /// Returns `None` for an empty path, as the root is not an [`OpenCliCommand`].
+    pub fn find_command(&self, path: &[&str]) -> Option<&OpenCliCommand> {
+        let (first, rest) = path.split_first()?;
+        let mut command = find_by_name(&self.commands, first)?;
+        for segment in rest {
+            command = find_by_name(&command.commands, segment)?;
+        }
+        Some(command)
+    }
+
+    /// Mutable version of [`find_command`](Self::find_command).
+    pub fn find_command_mut(&mut self, path: &[&str]) -> Option<&mut OpenCliCommand> {
+        let (first, rest) = path.split_first()?;
+        let mut command = find_by_name_mut(&mut self.commands, first)?;
+        for segment in rest {
+            command = find_by_name_mut(&mut command.commands, segment)?;
+        }
+        Some(command)
+    }
+
+    /// Find an option by name or alias among the options defined on the command at the given path.
+    ///
+    /// An empty path refers to the root options. Recursive options of parent commands are not
+    /// taken into account.
+    pub fn find_option(&self, path: &[&str], name: &str) -> Option<&OpenCliOption> {
+        let options = match path {
+            [] => &self.options,
+            _ => &self.find_command(path)?.options,
+        };
+        options
+            .iter()
+            .find(|option| option.name == name || option.aliases.iter().any(|alias| alias == name))
+    }
+
+    /// Find an argument by name on the command at the given path, an empty path referring to the root.
+    pub fn find_argument(&self, path: &[&str], name: &str) -> Option<&OpenCliArgument> {
+        let arguments = match path {
+            [] => &self.arguments,
+            _ => &self.find_command(path)?.arguments,
+        };
+        arguments.iter().find(|argument| argument.name == name)
+    }
+
+    /// Iterate over all commands in depth-first order, each with its path and parents.
+    pub fn iter_commands(&self) -> CommandIter<'_> {
+        CommandIter::new(&self.commands)
+    }
+}
+
+impl OpenCliCommand {
+    /// Iterate over all sub commands in depth-first order, with paths and parents relative to this command.
+    pub fn iter_commands(&self) -> CommandIter<'_> {
+        CommandIter::new(&self.commands)
+    }
+}
+
+fn matches(command: &OpenCliCommand, name: &str) -> bool {
+    command.name == name || command.aliases.iter().any(|alias| alias == name)
+}
+
+fn find_by_name<'a>(commands: &'a [OpenCliCommand], name: &str) -> Option<&'a OpenCliCommand> {
+    commands.iter().find(|command| matches(command, name))
+}
+
+fn find_by_name_mut<'a>(
+    commands: &'a mut [OpenCliCommand],
+    name: &str,
+) -> Option<&'a mut OpenCliCommand> {
+    commands.iter_mut().find(|command| matches(command, name))
+}
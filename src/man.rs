@@ -0,0 +1,342 @@
+//! Man page generation in roff format.
+//!
+//! One page is rendered for the root command and one for each visible sub command,
+//! named after the command path (e.g. `mytool-remote-add`).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use opencli::{OpenCliDocument, man};
+//!
+//! let opencli = OpenCliDocument::from_path("path/to/opencli.yaml").unwrap();
+//! for page in man::render(&opencli) {
+//!     std::fs::write(page.file_name(), page.content).unwrap();
+//! }
+//! ```
+
+use std::fmt::Write;
+
+use crate::tree::{Node, command_line, page_name, value_range};
+use crate::{OpenCliArgument, OpenCliDocument, OpenCliExitCode, OpenCliOption};
+
+/// The manual section used for the rendered pages.
+const SECTION: u8 = 1;
+
+/// A rendered man page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManPage {
+    /// The page name, e.g. `mytool-build`
+    pub name: String,
+
+    /// The roff source of the page
+    pub content: String,
+}
+
+impl ManPage {
+    /// The conventional file name of the page, e.g. `mytool-build.1`
+    pub fn file_name(&self) -> String {
+        format!("{}.{SECTION}", self.name)
+    }
+}
+
+/// Render the man pages for the root command and all visible sub commands.
+pub fn render(document: &OpenCliDocument) -> Vec<ManPage> {
+    Node::collect(document, false)
+        .iter()
+        .map(|node| page(document, node))
+        .collect()
+}
+
+fn page(document: &OpenCliDocument, node: &Node) -> ManPage {
+    let program = document.info.title.as_str();
+    let name = page_name(program, &node.path);
+    let mut roff = String::new();
+
+    writeln!(
+        roff,
+        ".TH {} {SECTION} \"\" {}",
+        escape(&name.to_uppercase()),
+        quote(&format!("{program} {}", document.info.version))
+    )
+    .unwrap();
+
+    let (summary, description, exit_codes, examples) = match node.command {
+        Some(command) => (
+            command
+                .description
+                .as_deref()
+                .and_then(|description| description.lines().next()),
+            command.description.as_deref(),
+            &command.exit_codes,
+            &command.examples,
+        ),
+        None => (
+            document.info.summary.as_deref(),
+            document.info.description.as_deref(),
+            &document.exit_codes,
+            &document.examples,
+        ),
+    };
+
+    writeln!(roff, ".SH NAME").unwrap();
+    match summary {
+        Some(summary) => writeln!(roff, "{} \\- {}", escape(&name), escape(summary)).unwrap(),
+        None => writeln!(roff, "{}", escape(&name)).unwrap(),
+    }
+
+    writeln!(roff, ".SH SYNOPSIS").unwrap();
+    synopsis(&mut roff, program, node);
+
+    if let Some(description) = description {
+        writeln!(roff, ".SH DESCRIPTION").unwrap();
+        paragraph(&mut roff, description);
+    }
+
+    if !node.options.is_empty() {
+        writeln!(roff, ".SH OPTIONS").unwrap();
+        for option in &node.options {
+            option_entry(&mut roff, option);
+        }
+    }
+
+    if !node.arguments.is_empty() {
+        writeln!(roff, ".SH ARGUMENTS").unwrap();
+        for argument in &node.arguments {
+            writeln!(roff, ".TP").unwrap();
+            writeln!(roff, "\\fI{}\\fR", escape(&argument.name)).unwrap();
+            details(
+                &mut roff,
+                argument.description.as_deref(),
+                argument.required.unwrap_or(false),
+                &argument.accepted_values,
+            );
+        }
+    }
+
+    if !node.commands.is_empty() {
+        writeln!(roff, ".SH COMMANDS").unwrap();
+        for command in &node.commands {
+            let mut path = node.path.clone();
+            path.push(&command.name);
+            writeln!(roff, ".TP").unwrap();
+            let names: Vec<String> = std::iter::once(&command.name)
+                .chain(&command.aliases)
+                .map(|name| format!("\\fB{}\\fR", escape(name)))
+                .collect();
+            writeln!(roff, "{}", names.join(", ")).unwrap();
+            if let Some(description) = &command.description {
+                writeln!(roff, "{}", escape(description)).unwrap();
+                writeln!(roff, ".br").unwrap();
+            }
+            writeln!(
+                roff,
+                "See \\fB{}\\fR({SECTION}).",
+                escape(&page_name(program, &path))
+            )
+            .unwrap();
+        }
+    }
+
+    if !exit_codes.is_empty() {
+        writeln!(roff, ".SH \"EXIT STATUS\"").unwrap();
+        exit_status(&mut roff, exit_codes);
+    }
+
+    if !examples.is_empty() {
+        writeln!(roff, ".SH EXAMPLES").unwrap();
+        for example in examples {
+            writeln!(roff, ".PP").unwrap();
+            writeln!(roff, ".nf").unwrap();
+            for line in example.lines() {
+                writeln!(roff, "{}", escape(line)).unwrap();
+            }
+            writeln!(roff, ".fi").unwrap();
+        }
+    }
+
+    if let Some(contact) = &document.info.contact {
+        let mut author = Vec::new();
+        if let Some(name) = &contact.name {
+            author.push(escape(name));
+        }
+        if let Some(email) = &contact.email {
+            author.push(format!("<{}>", escape(email)));
+        }
+        if let Some(url) = &contact.url {
+            author.push(format!("<{}>", escape(url)));
+        }
+        if !author.is_empty() {
+            writeln!(roff, ".SH AUTHOR").unwrap();
+            writeln!(roff, "{}", author.join(" ")).unwrap();
+        }
+    }
+
+    if let Some(license) = &document.info.license {
+        let copyright = match (&license.name, &license.identifier) {
+            (Some(name), Some(identifier)) => Some(format!("{name} ({identifier})")),
+            (Some(name), None) => Some(name.clone()),
+            (None, Some(identifier)) => Some(identifier.clone()),
+            (None, None) => None,
+        };
+        if let Some(copyright) = copyright {
+            writeln!(roff, ".SH COPYRIGHT").unwrap();
+            writeln!(roff, "Licensed under {}.", escape(&copyright)).unwrap();
+        }
+    }
+
+    let mut see_also = Vec::new();
+    if let Some((_, parent)) = node.path.split_last() {
+        see_also.push(page_name(program, parent));
+    }
+    for command in &node.commands {
+        let mut path = node.path.clone();
+        path.push(&command.name);
+        see_also.push(page_name(program, &path));
+    }
+    if !see_also.is_empty() {
+        writeln!(roff, ".SH \"SEE ALSO\"").unwrap();
+        let references: Vec<String> = see_also
+            .iter()
+            .map(|name| format!("\\fB{}\\fR({SECTION})", escape(name)))
+            .collect();
+        writeln!(roff, "{}", references.join(", ")).unwrap();
+    }
+
+    ManPage {
+        name,
+        content: roff,
+    }
+}
+
+fn synopsis(roff: &mut String, program: &str, node: &Node) {
+    writeln!(roff, ".B {}", escape(&command_line(program, &node.path))).unwrap();
+
+    for option in &node.options {
+        let names: Vec<String> = std::iter::once(&option.name)
+            .chain(&option.aliases)
+            .map(|name| format!("\\fB{}\\fR", escape(name)))
+            .collect();
+        let mut synopsis = names.join("|");
+        for argument in &option.arguments {
+            synopsis.push(' ');
+            synopsis.push_str(&argument_synopsis(argument, false));
+        }
+        if option.required.unwrap_or(false) {
+            writeln!(roff, "{synopsis}").unwrap();
+        } else {
+            writeln!(roff, "[{synopsis}]").unwrap();
+        }
+    }
+
+    for argument in &node.arguments {
+        writeln!(roff, "{}", argument_synopsis(argument, true)).unwrap();
+    }
+
+    if !node.commands.is_empty() {
+        writeln!(roff, "\\fICOMMAND\\fR").unwrap();
+    }
+}
+
+/// The synopsis of an argument. Option arguments are optional only if their arity allows no value.
+fn argument_synopsis(argument: &OpenCliArgument, positional: bool) -> String {
+    let (minimum, maximum) = value_range(argument);
+    let mut synopsis = format!("\\fI{}\\fR", escape(&argument.name));
+    if maximum.is_none_or(|maximum| maximum > 1) {
+        synopsis.push_str("...");
+    }
+    if minimum > 0 && (!positional || argument.required.unwrap_or(false)) {
+        synopsis
+    } else {
+        format!("[{synopsis}]")
+    }
+}
+
+fn option_entry(roff: &mut String, option: &OpenCliOption) {
+    writeln!(roff, ".TP").unwrap();
+    let names: Vec<String> = std::iter::once(&option.name)
+        .chain(&option.aliases)
+        .map(|name| format!("\\fB{}\\fR", escape(name)))
+        .collect();
+    let mut header = names.join(", ");
+    for argument in &option.arguments {
+        header.push(' ');
+        header.push_str(&argument_synopsis(argument, false));
+    }
+    writeln!(roff, "{header}").unwrap();
+
+    let accepted_values: Vec<String> = option
+        .arguments
+        .iter()
+        .flat_map(|argument| argument.accepted_values.iter().cloned())
+        .collect();
+    details(
+        roff,
+        option.description.as_deref(),
+        option.required.unwrap_or(false),
+        &accepted_values,
+    );
+}
+
+fn details(
+    roff: &mut String,
+    description: Option<&str>,
+    required: bool,
+    accepted_values: &[String],
+) {
+    let mut lines = Vec::new();
+    if let Some(description) = description {
+        lines.push(escape(description));
+    }
+    if required {
+        lines.push("This value is required.".to_owned());
+    }
+    if !accepted_values.is_empty() {
+        let values: Vec<String> = accepted_values
+            .iter()
+            .map(|value| format!("\\fB{}\\fR", escape(value)))
+            .collect();
+        lines.push(format!("Possible values: {}", values.join(", ")));
+    }
+    writeln!(roff, "{}", lines.join("\n.br\n")).unwrap();
+}
+
+fn exit_status(roff: &mut String, exit_codes: &[OpenCliExitCode]) {
+    for exit_code in exit_codes {
+        writeln!(roff, ".TP").unwrap();
+        writeln!(roff, "\\fB{}\\fR", exit_code.code).unwrap();
+        if let Some(description) = &exit_code.description {
+            writeln!(roff, "{}", escape(description)).unwrap();
+        }
+    }
+}
+
+fn paragraph(roff: &mut String, text: &str) {
+    for (i, block) in text.split("\n\n").enumerate() {
+        if i > 0 {
+            writeln!(roff, ".PP").unwrap();
+        }
+        for line in block.lines() {
+            writeln!(roff, "{}", escape(line)).unwrap();
+        }
+    }
+}
+
+/// Escape text for use in roff, protecting lines which would otherwise start a request.
+fn escape(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            let escaped = line.replace('\\', "\\e").replace('-', "\\-");
+            if escaped.starts_with('.') || escaped.starts_with('\'') {
+                format!("\\&{escaped}")
+            } else {
+                escaped
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Quote a macro argument.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", escape(text).replace('"', "\"\""))
+}
@@ -0,0 +1,280 @@
+//! Markdown reference documentation generation.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use opencli::{OpenCliDocument, markdown::{self, MarkdownOptions}};
+//!
+//! let opencli = OpenCliDocument::from_path("path/to/opencli.yaml").unwrap();
+//! let options = MarkdownOptions {
+//!     split: true,
+//!     ..Default::default()
+//! };
+//! for file in markdown::render(&opencli, &options) {
+//!     std::fs::write(file.name, file.content).unwrap();
+//! }
+//! ```
+
+use std::fmt::Write;
+
+use crate::tree::{Node, command_line, page_name, value_range};
+use crate::usage::node_usage;
+use crate::{OpenCliArgument, OpenCliDocument, OpenCliOption};
+
+/// Options controlling the rendered Markdown.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MarkdownOptions {
+    /// Whether or not hidden commands, options and arguments are included
+    pub include_hidden: bool,
+
+    /// Whether to render one file per command instead of a single file with one section per command
+    pub split: bool,
+}
+
+/// A rendered Markdown file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownFile {
+    /// The file name, e.g. `mytool-build.md`
+    pub name: String,
+
+    /// The Markdown content of the file
+    pub content: String,
+}
+
+/// Render the reference documentation of a document.
+pub fn render(document: &OpenCliDocument, options: &MarkdownOptions) -> Vec<MarkdownFile> {
+    let program = document.info.title.as_str();
+    let nodes = Node::collect(document, options.include_hidden);
+
+    if options.split {
+        nodes
+            .iter()
+            .map(|node| {
+                let mut content = String::new();
+                section(&mut content, document, node, 1, true);
+                MarkdownFile {
+                    name: format!("{}.md", page_name(program, &node.path)),
+                    content,
+                }
+            })
+            .collect()
+    } else {
+        let mut content = String::new();
+        for (i, node) in nodes.iter().enumerate() {
+            if i > 0 {
+                writeln!(content).unwrap();
+            }
+            let level = if node.path.is_empty() { 1 } else { 2 };
+            section(&mut content, document, node, level, false);
+        }
+        vec![MarkdownFile {
+            name: format!("{program}.md"),
+            content,
+        }]
+    }
+}
+
+fn section(out: &mut String, document: &OpenCliDocument, node: &Node, level: usize, split: bool) {
+    let program = document.info.title.as_str();
+    let heading = "#".repeat(level);
+    let subheading = "#".repeat(level + 1);
+
+    writeln!(out, "{heading} {}", command_line(program, &node.path)).unwrap();
+    writeln!(out).unwrap();
+
+    match node.command {
+        Some(command) => {
+            if let Some(description) = &command.description {
+                writeln!(out, "{description}").unwrap();
+                writeln!(out).unwrap();
+            }
+            if !command.aliases.is_empty() {
+                writeln!(out, "Aliases: {}", code_list(&command.aliases)).unwrap();
+                writeln!(out).unwrap();
+            }
+        }
+        None => {
+            if let Some(summary) = &document.info.summary {
+                writeln!(out, "{summary}").unwrap();
+                writeln!(out).unwrap();
+            }
+            writeln!(out, "Version: `{}`", document.info.version).unwrap();
+            writeln!(out).unwrap();
+            if let Some(description) = &document.info.description {
+                writeln!(out, "{}", description.trim_end()).unwrap();
+                writeln!(out).unwrap();
+            }
+        }
+    }
+
+    writeln!(out, "```text").unwrap();
+    writeln!(out, "{}", node_usage(document, node)).unwrap();
+    writeln!(out, "```").unwrap();
+    writeln!(out).unwrap();
+
+    let (exit_codes, examples) = match node.command {
+        Some(command) => (&command.exit_codes, &command.examples),
+        None => (&document.exit_codes, &document.examples),
+    };
+
+    if !node.commands.is_empty() {
+        writeln!(out, "{subheading} Commands").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "| Command | Aliases | Description |").unwrap();
+        writeln!(out, "| --- | --- | --- |").unwrap();
+        for command in &node.commands {
+            let mut path = node.path.clone();
+            path.push(&command.name);
+            let target = if split {
+                format!("{}.md", page_name(program, &path))
+            } else {
+                format!("#{}", anchor(&command_line(program, &path)))
+            };
+            writeln!(
+                out,
+                "| [`{}`]({target}) | {} | {} |",
+                command.name,
+                code_list(&command.aliases),
+                cell(command.description.as_deref().unwrap_or_default())
+            )
+            .unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+
+    if !node.options.is_empty() {
+        writeln!(out, "{subheading} Options").unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "| Option | Aliases | Arguments | Required | Description |"
+        )
+        .unwrap();
+        writeln!(out, "| --- | --- | --- | --- | --- |").unwrap();
+        for option in &node.options {
+            option_row(out, option);
+        }
+        writeln!(out).unwrap();
+    }
+
+    if !node.arguments.is_empty() {
+        writeln!(out, "{subheading} Arguments").unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "| Argument | Arity | Required | Accepted values | Description |"
+        )
+        .unwrap();
+        writeln!(out, "| --- | --- | --- | --- | --- |").unwrap();
+        for argument in &node.arguments {
+            writeln!(
+                out,
+                "| `{}` | {} | {} | {} | {} |",
+                argument.name,
+                arity(argument),
+                yes_no(argument.required),
+                code_list(&argument.accepted_values),
+                cell(argument.description.as_deref().unwrap_or_default())
+            )
+            .unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+
+    if !exit_codes.is_empty() {
+        writeln!(out, "{subheading} Exit codes").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "| Code | Description |").unwrap();
+        writeln!(out, "| --- | --- |").unwrap();
+        for exit_code in exit_codes {
+            writeln!(
+                out,
+                "| `{}` | {} |",
+                exit_code.code,
+                cell(exit_code.description.as_deref().unwrap_or_default())
+            )
+            .unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+
+    if !examples.is_empty() {
+        writeln!(out, "{subheading} Examples").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "```sh").unwrap();
+        for example in examples {
+            writeln!(out, "{example}").unwrap();
+        }
+        writeln!(out, "```").unwrap();
+        writeln!(out).unwrap();
+    }
+
+    // Keep a single trailing newline
+    while out.ends_with("\n\n") {
+        out.pop();
+    }
+}
+
+fn option_row(out: &mut String, option: &OpenCliOption) {
+    let arguments: Vec<String> = option
+        .arguments
+        .iter()
+        .map(|argument| {
+            let mut text = format!("`{}` ({})", argument.name, arity(argument));
+            if !argument.accepted_values.is_empty() {
+                write!(text, ": {}", code_list(&argument.accepted_values)).unwrap();
+            }
+            text
+        })
+        .collect();
+
+    writeln!(
+        out,
+        "| `{}` | {} | {} | {} | {} |",
+        option.name,
+        code_list(&option.aliases),
+        arguments.join("<br>"),
+        yes_no(option.required),
+        cell(option.description.as_deref().unwrap_or_default())
+    )
+    .unwrap();
+}
+
+/// Format the arity of an argument, e.g. `1`, `0..1` or `1..`.
+fn arity(argument: &OpenCliArgument) -> String {
+    match value_range(argument) {
+        (minimum, Some(maximum)) if minimum == maximum => minimum.to_string(),
+        (minimum, Some(maximum)) => format!("{minimum}..{maximum}"),
+        (minimum, None) => format!("{minimum}.."),
+    }
+}
+
+fn yes_no(value: Option<bool>) -> &'static str {
+    if value.unwrap_or(false) { "yes" } else { "no" }
+}
+
+fn code_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| format!("`{value}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Escape text for use in a table cell.
+fn cell(text: &str) -> String {
+    text.trim().replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// The GitHub style anchor of a heading.
+fn anchor(heading: &str) -> String {
+    heading
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
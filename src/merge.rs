@@ -0,0 +1,386 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::tree::{arity_range, group_options, option_argument_separator};
+use crate::{
+    OpenCliArgument, OpenCliCommand, OpenCliDocument, OpenCliExitCode, OpenCliMetadata,
+    OpenCliOption,
+};
+
+/// A value which differs between the two documents passed to [`OpenCliDocument::merge`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// The location of the value in the merged document, e.g. `commands[1].options[0].arguments[0].arity`
+    pub path: String,
+
+    /// The kind of conflict
+    pub kind: MergeConflictKind,
+}
+
+/// The kinds of conflicts reported by [`OpenCliDocument::merge`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MergeConflictKind {
+    #[error("Conflicting values {base} and {overlay}")]
+    Value {
+        base: serde_json::Value,
+        overlay: serde_json::Value,
+    },
+    #[error("Name `{0}` matches more than one item")]
+    AmbiguousName(String),
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+impl std::error::Error for MergeConflict {}
+
+impl OpenCliDocument {
+    /// Combine two documents, e.g. a vendor document with local additions or the command trees
+    /// of several teams.
+    ///
+    /// Commands and options are matched by name or alias, arguments by name, metadata by name
+    /// and exit codes by code. Items only present in the overlay are appended, aliases, examples
+    /// and exit codes are combined, as are unknown fields by key. For matched items, the
+    /// documentation of the overlay wins: the info fields, descriptions, groups and the `hidden`
+    /// and `interactive` flags. The values which change how a command line is parsed must have
+    /// the same effect where the overlay sets them, defaults included: arities, accepted values,
+    /// option arguments, the `required` and `recursive` flags and the conventions. All
+    /// disagreements are returned as conflicts instead of a merged document.
+    pub fn merge(
+        base: &OpenCliDocument,
+        overlay: &OpenCliDocument,
+    ) -> Result<Self, Vec<MergeConflict>> {
+        let mut merger = Merger::default();
+        let mut document = base.clone();
+
+        overwrite_string(&mut document.opencli, &overlay.opencli);
+        overwrite_string(&mut document.info.title, &overlay.info.title);
+        overwrite_string(&mut document.info.version, &overlay.info.version);
+        overwrite(&mut document.info.summary, &overlay.info.summary);
+        overwrite(&mut document.info.description, &overlay.info.description);
+        overwrite(&mut document.info.contact, &overlay.info.contact);
+        overwrite(&mut document.info.license, &overlay.info.license);
+        if let Some(overlay) = &overlay.conventions {
+            // Unset conventions of the base are the defaults
+            let conventions = document.conventions.get_or_insert_with(Default::default);
+            extensions(&mut conventions.extensions, &overlay.extensions);
+            merger.agree(
+                "conventions.groupOptions",
+                &mut conventions.group_options,
+                &overlay.group_options,
+                group_options,
+            );
+            merger.agree(
+                "conventions.optionArgumentSeparator",
+                &mut conventions.option_argument_separator,
+                &overlay.option_argument_separator,
+                |separator| option_argument_separator(separator).to_owned(),
+            );
+        }
+        overwrite(&mut document.interactive, &overlay.interactive);
+
+        merger.arguments("", &mut document.arguments, &overlay.arguments);
+        merger.options("", &mut document.options, &overlay.options);
+        merger.commands("", &mut document.commands, &overlay.commands);
+        exit_codes(&mut document.exit_codes, &overlay.exit_codes);
+        union(&mut document.examples, &overlay.examples);
+        metadata(&mut document.metadata, &overlay.metadata);
+        extensions(&mut document.info.extensions, &overlay.info.extensions);
+        extensions(&mut document.extensions, &overlay.extensions);
+
+        if merger.conflicts.is_empty() {
+            Ok(document)
+        } else {
+            Err(merger.conflicts)
+        }
+    }
+}
+
+#[derive(Default)]
+struct Merger {
+    conflicts: Vec<MergeConflict>,
+}
+
+impl Merger {
+    fn conflict(&mut self, path: String, kind: MergeConflictKind) {
+        self.conflicts.push(MergeConflict { path, kind });
+    }
+
+    /// Check that a value set by the overlay has the same effect as the base value, taking
+    /// defaults into account with `effective`, and take the overlay value if the base is unset.
+    fn agree<T: Clone + Serialize, E: PartialEq>(
+        &mut self,
+        path: &str,
+        base: &mut Option<T>,
+        overlay: &Option<T>,
+        effective: impl Fn(&Option<T>) -> E,
+    ) {
+        if overlay.is_none() {
+            return;
+        }
+        if effective(base) != effective(overlay) {
+            self.conflict(
+                path.to_owned(),
+                MergeConflictKind::Value {
+                    base: serde_json::to_value(&*base).unwrap_or_default(),
+                    overlay: serde_json::to_value(overlay).unwrap_or_default(),
+                },
+            );
+        } else if base.is_none() {
+            base.clone_from(overlay);
+        }
+    }
+
+    fn commands(
+        &mut self,
+        prefix: &str,
+        base: &mut Vec<OpenCliCommand>,
+        overlay: &[OpenCliCommand],
+    ) {
+        for command in overlay {
+            let overlay_names = names(&command.name, &command.aliases);
+            let found = self.find(prefix, "commands", &overlay_names, base, |base| {
+                names(&base.name, &base.aliases)
+            });
+            let Some(found) = found else {
+                continue;
+            };
+            let Some(i) = found else {
+                base.push(command.clone());
+                continue;
+            };
+
+            let path = join(prefix, &format!("commands[{i}]"));
+            let merged = &mut base[i];
+            union(&mut merged.aliases, &command.aliases);
+            merged.aliases.retain(|alias| *alias != merged.name);
+            overwrite(&mut merged.description, &command.description);
+            overwrite(&mut merged.hidden, &command.hidden);
+            overwrite(&mut merged.interactive, &command.interactive);
+            self.arguments(&path, &mut merged.arguments, &command.arguments);
+            self.options(&path, &mut merged.options, &command.options);
+            self.commands(&path, &mut merged.commands, &command.commands);
+            exit_codes(&mut merged.exit_codes, &command.exit_codes);
+            union(&mut merged.examples, &command.examples);
+            metadata(&mut merged.metadata, &command.metadata);
+            extensions(&mut merged.extensions, &command.extensions);
+        }
+    }
+
+    fn options(&mut self, prefix: &str, base: &mut Vec<OpenCliOption>, overlay: &[OpenCliOption]) {
+        for option in overlay {
+            let overlay_names = names(&option.name, &option.aliases);
+            let found = self.find(prefix, "options", &overlay_names, base, |base| {
+                names(&base.name, &base.aliases)
+            });
+            let Some(found) = found else {
+                continue;
+            };
+            let Some(i) = found else {
+                base.push(option.clone());
+                continue;
+            };
+
+            let path = join(prefix, &format!("options[{i}]"));
+            let merged = &mut base[i];
+            union(&mut merged.aliases, &option.aliases);
+            merged.aliases.retain(|alias| *alias != merged.name);
+            self.agree(
+                &join(&path, "required"),
+                &mut merged.required,
+                &option.required,
+                or_false,
+            );
+            self.agree(
+                &join(&path, "recursive"),
+                &mut merged.recursive,
+                &option.recursive,
+                or_false,
+            );
+            overwrite(&mut merged.group, &option.group);
+            overwrite(&mut merged.description, &option.description);
+            overwrite(&mut merged.hidden, &option.hidden);
+            metadata(&mut merged.metadata, &option.metadata);
+            extensions(&mut merged.extensions, &option.extensions);
+
+            // Different option arguments change how many values the option takes, if any
+            let argument_names = |arguments: &[OpenCliArgument]| -> Vec<String> {
+                arguments
+                    .iter()
+                    .map(|argument| argument.name.clone())
+                    .collect()
+            };
+            let (base_names, overlay_names) = (
+                argument_names(&merged.arguments),
+                argument_names(&option.arguments),
+            );
+            if base_names != overlay_names {
+                self.conflict(
+                    join(&path, "arguments"),
+                    MergeConflictKind::Value {
+                        base: base_names.into(),
+                        overlay: overlay_names.into(),
+                    },
+                );
+            } else {
+                self.arguments(&path, &mut merged.arguments, &option.arguments);
+            }
+        }
+    }
+
+    fn arguments(
+        &mut self,
+        prefix: &str,
+        base: &mut Vec<OpenCliArgument>,
+        overlay: &[OpenCliArgument],
+    ) {
+        for argument in overlay {
+            let Some(i) = base.iter().position(|base| base.name == argument.name) else {
+                base.push(argument.clone());
+                continue;
+            };
+
+            let path = join(prefix, &format!("arguments[{i}]"));
+            let merged = &mut base[i];
+            self.agree(
+                &join(&path, "required"),
+                &mut merged.required,
+                &argument.required,
+                or_false,
+            );
+            self.agree(
+                &join(&path, "arity"),
+                &mut merged.arity,
+                &argument.arity,
+                arity_range,
+            );
+            let mut accepted_values = non_empty(&merged.accepted_values);
+            self.agree(
+                &join(&path, "acceptedValues"),
+                &mut accepted_values,
+                &non_empty(&argument.accepted_values),
+                |values| values.iter().flatten().cloned().collect::<BTreeSet<_>>(),
+            );
+            merged.accepted_values = accepted_values.unwrap_or_default();
+            overwrite(&mut merged.group, &argument.group);
+            overwrite(&mut merged.description, &argument.description);
+            overwrite(&mut merged.hidden, &argument.hidden);
+            metadata(&mut merged.metadata, &argument.metadata);
+            extensions(&mut merged.extensions, &argument.extensions);
+        }
+    }
+
+    /// Find the base item sharing a name or alias with an overlay item.
+    ///
+    /// Returns `Some(None)` if there is none and `None` after reporting a conflict if there are
+    /// several.
+    fn find<T>(
+        &mut self,
+        prefix: &str,
+        field: &str,
+        names: &[&str],
+        base: &[T],
+        names_of: impl Fn(&T) -> Vec<&str>,
+    ) -> Option<Option<usize>> {
+        let mut matches = base
+            .iter()
+            .enumerate()
+            .filter(|(_, base)| names_of(base).iter().any(|name| names.contains(name)))
+            .map(|(i, _)| i);
+        let first = matches.next();
+        if matches.next().is_some() {
+            let path = join(prefix, &format!("{field}[{}]", first.unwrap_or_default()));
+            self.conflict(path, MergeConflictKind::AmbiguousName(names[0].to_owned()));
+            return None;
+        }
+        Some(first)
+    }
+}
+
+fn names<'a>(name: &'a str, aliases: &'a [String]) -> Vec<&'a str> {
+    std::iter::once(name)
+        .chain(aliases.iter().map(String::as_str))
+        .collect()
+}
+
+fn or_false(value: &Option<bool>) -> bool {
+    value.unwrap_or(false)
+}
+
+/// Take the overlay value if it is set.
+fn overwrite<T: Clone>(base: &mut Option<T>, overlay: &Option<T>) {
+    if overlay.is_some() {
+        base.clone_from(overlay);
+    }
+}
+
+/// Take the overlay value if it is not empty.
+fn overwrite_string(base: &mut String, overlay: &str) {
+    if !overlay.is_empty() {
+        overlay.clone_into(base);
+    }
+}
+
+/// Append the overlay values missing in the base.
+fn union(base: &mut Vec<String>, overlay: &[String]) {
+    for value in overlay {
+        if !base.contains(value) {
+            base.push(value.clone());
+        }
+    }
+}
+
+/// Combine exit codes by code, taking the overlay description if set.
+fn exit_codes(base: &mut Vec<OpenCliExitCode>, overlay: &[OpenCliExitCode]) {
+    for exit_code in overlay {
+        match base.iter_mut().find(|base| base.code == exit_code.code) {
+            Some(merged) => {
+                overwrite(&mut merged.description, &exit_code.description);
+                extensions(&mut merged.extensions, &exit_code.extensions);
+            }
+            None => base.push(exit_code.clone()),
+        }
+    }
+}
+
+/// Combine metadata by name, taking the overlay value if set.
+fn metadata(base: &mut Vec<OpenCliMetadata>, overlay: &[OpenCliMetadata]) {
+    for metadata in overlay {
+        match base.iter_mut().find(|base| base.name == metadata.name) {
+            Some(merged) => {
+                overwrite(&mut merged.value, &metadata.value);
+                extensions(&mut merged.extensions, &metadata.extensions);
+            }
+            None => base.push(metadata.clone()),
+        }
+    }
+}
+
+/// Combine unknown fields by key, taking the overlay value.
+fn extensions(
+    base: &mut serde_json::Map<String, serde_json::Value>,
+    overlay: &serde_json::Map<String, serde_json::Value>,
+) {
+    for (key, value) in overlay {
+        base.insert(key.clone(), value.clone());
+    }
+}
+
+/// Accepted values as an optional value, an empty list accepting anything.
+fn non_empty(values: &[String]) -> Option<Vec<String>> {
+    (!values.is_empty()).then(|| values.to_vec())
+}
+
+fn join(prefix: &str, segment: &str) -> String {
+    if prefix.is_empty() {
+        segment.to_owned()
+    } else {
+        format!("{prefix}.{segment}")
+    }
+}
@@ -0,0 +1,13 @@
+use crate::OpenCliDocument;
+
+impl OpenCliDocument {
+    /// The JSON Schema of an OpenCLI document, as accepted and produced by this crate.
+    ///
+    /// Descriptions are taken from the field documentation, and fields which may be omitted
+    /// are not required. Editors can use the schema to validate and complete documents.
+    pub fn json_schema() -> serde_json::Value {
+        let mut schema = schemars::schema_for!(OpenCliDocument);
+        schema.insert("title".to_owned(), "OpenCLI".into());
+        schema.to_value()
+    }
+}
@@ -0,0 +1,198 @@
+//! Internal helpers for walking the command tree of a document.
+
+use crate::{OpenCliArgument, OpenCliArity, OpenCliCommand, OpenCliDocument, OpenCliOption};
+
+/// A command of the document together with its children.
+///
+/// Unless collected with `include_hidden`, hidden commands, options and arguments are left out.
+pub(crate) struct Node<'a> {
+    /// The command names leading to this command, empty for the root
+    pub(crate) path: Vec<&'a str>,
+    /// The command itself, `None` for the root
+    pub(crate) command: Option<&'a OpenCliCommand>,
+    /// The visible sub commands
+    pub(crate) commands: Vec<&'a OpenCliCommand>,
+    /// The visible options, including recursive options of the parent commands
+    pub(crate) options: Vec<&'a OpenCliOption>,
+    /// The visible arguments
+    pub(crate) arguments: Vec<&'a OpenCliArgument>,
+}
+
+impl<'a> Node<'a> {
+    pub(crate) fn collect(document: &'a OpenCliDocument, include_hidden: bool) -> Vec<Self> {
+        let mut nodes = Vec::new();
+        Self::visit(&mut nodes, include_hidden, document, Vec::new(), None, &[]);
+        nodes
+    }
+
+    fn visit(
+        nodes: &mut Vec<Self>,
+        include_hidden: bool,
+        document: &'a OpenCliDocument,
+        path: Vec<&'a str>,
+        command: Option<&'a OpenCliCommand>,
+        inherited: &[&'a OpenCliOption],
+    ) {
+        let (options, arguments, commands) = match command {
+            Some(command) => (&command.options, &command.arguments, &command.commands),
+            None => (&document.options, &document.arguments, &document.commands),
+        };
+
+        // Options defined closer to the command shadow inherited ones sharing a name or alias
+        let mut effective: Vec<&OpenCliOption> = inherited
+            .iter()
+            .filter(|inherited| !options.iter().any(|option| shares_name(option, inherited)))
+            .copied()
+            .collect();
+        effective.extend(options.iter());
+
+        let recursive: Vec<&OpenCliOption> = effective
+            .iter()
+            .filter(|option| option.recursive.unwrap_or(false))
+            .copied()
+            .collect();
+        let commands: Vec<&OpenCliCommand> = commands
+            .iter()
+            .filter(|command| include_hidden || !command.hidden.unwrap_or(false))
+            .collect();
+
+        nodes.push(Node {
+            path: path.clone(),
+            command,
+            commands: commands.clone(),
+            options: effective
+                .into_iter()
+                .filter(|option| include_hidden || !option.hidden.unwrap_or(false))
+                .collect(),
+            arguments: arguments
+                .iter()
+                .filter(|argument| include_hidden || !argument.hidden.unwrap_or(false))
+                .collect(),
+        });
+
+        for command in commands {
+            let mut path = path.clone();
+            path.push(&command.name);
+            Self::visit(
+                nodes,
+                include_hidden,
+                document,
+                path,
+                Some(command),
+                &recursive,
+            );
+        }
+    }
+
+    /// The names and aliases of the visible sub commands.
+    pub(crate) fn command_names(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.commands
+            .iter()
+            .flat_map(|command| names(&command.name, &command.aliases))
+    }
+
+    /// The names and aliases of the visible options.
+    pub(crate) fn option_names(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.options
+            .iter()
+            .flat_map(|option| names(&option.name, &option.aliases))
+    }
+
+    /// The accepted values of the visible arguments.
+    pub(crate) fn argument_values(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.arguments
+            .iter()
+            .flat_map(|argument| argument.accepted_values.iter().map(String::as_str))
+    }
+}
+
+/// Resolve a path of command names or aliases to the command names.
+pub(crate) fn canonical_path<'a>(
+    document: &'a OpenCliDocument,
+    path: &[&str],
+) -> Option<Vec<&'a str>> {
+    let mut commands = &document.commands;
+    let mut names = Vec::with_capacity(path.len());
+    for segment in path {
+        let command = commands.iter().find(|command| {
+            command.name == *segment || command.aliases.iter().any(|alias| alias == segment)
+        })?;
+        names.push(command.name.as_str());
+        commands = &command.commands;
+    }
+    Some(names)
+}
+
+/// The command line invoking the command at the given path, e.g. `mytool remote add`.
+pub(crate) fn command_line(program: &str, path: &[&str]) -> String {
+    std::iter::once(program)
+        .chain(path.iter().copied())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The name of the page documenting the command at the given path, e.g. `mytool-remote-add`.
+pub(crate) fn page_name(program: &str, path: &[&str]) -> String {
+    std::iter::once(program)
+        .chain(path.iter().copied())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+pub(crate) fn names<'a>(name: &'a str, aliases: &'a [String]) -> impl Iterator<Item = &'a str> {
+    std::iter::once(name).chain(aliases.iter().map(String::as_str))
+}
+
+/// Whether two options have a name or alias in common.
+pub(crate) fn shares_name(a: &OpenCliOption, b: &OpenCliOption) -> bool {
+    names(&a.name, &a.aliases).any(|name| names(&b.name, &b.aliases).any(|other| other == name))
+}
+
+/// Whether the option expects at least one value.
+pub(crate) fn takes_value(option: &OpenCliOption) -> bool {
+    option
+        .arguments
+        .iter()
+        .any(|argument| value_range(argument).1 != Some(0))
+}
+
+/// The minimum and maximum number of values of an argument, `None` meaning unbounded.
+///
+/// Arguments without an arity take exactly one value, a missing minimum defaults to one.
+pub(crate) fn value_range(argument: &OpenCliArgument) -> (usize, Option<usize>) {
+    arity_range(&argument.arity)
+}
+
+/// The value count range of an argument with the given arity, see [`value_range`].
+pub(crate) fn arity_range(arity: &Option<OpenCliArity>) -> (usize, Option<usize>) {
+    match arity {
+        Some(arity) => {
+            let maximum = arity.maximum.map(|maximum| maximum.max(0) as usize);
+            let minimum = arity.minimum.unwrap_or(1).max(0) as usize;
+            (
+                maximum.map_or(minimum, |maximum| minimum.min(maximum)),
+                maximum,
+            )
+        }
+        None => (1, Some(1)),
+    }
+}
+
+/// Whether short options can be grouped, the OpenCLI default being `true`.
+pub(crate) fn group_options(group_options: &Option<bool>) -> bool {
+    group_options.unwrap_or(true)
+}
+
+/// The separator between an option and its value, the OpenCLI default being a space.
+pub(crate) fn option_argument_separator(separator: &Option<String>) -> &str {
+    separator.as_deref().unwrap_or(" ")
+}
+
+/// The accepted values of all arguments of an option.
+pub(crate) fn option_values(option: &OpenCliOption) -> Vec<&str> {
+    option
+        .arguments
+        .iter()
+        .flat_map(|argument| argument.accepted_values.iter().map(String::as_str))
+        .collect()
+}
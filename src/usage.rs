@@ -0,0 +1,159 @@
+use std::fmt::Write;
+
+use crate::tree::{Node, canonical_path, command_line, value_range};
+use crate::{OpenCliArgument, OpenCliCommand, OpenCliConventions, OpenCliDocument, OpenCliOption};
+
+impl OpenCliDocument {
+    /// Render the usage line of the root command, e.g. `mytool [OPTIONS] <COMMAND>`.
+    ///
+    /// The program name is taken from [`OpenCliInfo::title`](crate::OpenCliInfo::title).
+    pub fn usage(&self) -> String {
+        render(
+            &self.info.title,
+            self.options.iter(),
+            self.arguments.iter(),
+            self.commands.iter(),
+            self.conventions.as_ref(),
+        )
+    }
+
+    /// Render the usage line of the command at the given path, e.g. `mytool build [OPTIONS] <target>...`.
+    ///
+    /// The path consists of command names or aliases. Recursive options of the parent commands are
+    /// taken into account. Returns `None` if no command exists at the path.
+    pub fn command_usage(&self, path: &[&str]) -> Option<String> {
+        let path = canonical_path(self, path)?;
+        let nodes = Node::collect(self, true);
+        let node = nodes.iter().find(|node| node.path == path)?;
+        Some(node_usage(self, node))
+    }
+}
+
+impl OpenCliCommand {
+    /// Render the usage line of the command, e.g. `mytool build [OPTIONS] <target>... [--] [<extra>]`.
+    ///
+    /// The prefix is the command line leading up to this command, e.g. `mytool` or `mytool remote`.
+    pub fn usage(&self, prefix: &str, conventions: Option<&OpenCliConventions>) -> String {
+        let command_line = if prefix.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{prefix} {}", self.name)
+        };
+        render(
+            &command_line,
+            self.options.iter(),
+            self.arguments.iter(),
+            self.commands.iter(),
+            conventions,
+        )
+    }
+}
+
+/// The usage line of a collected node, including inherited options.
+pub(crate) fn node_usage(document: &OpenCliDocument, node: &Node) -> String {
+    render(
+        &command_line(&document.info.title, &node.path),
+        node.options.iter().copied(),
+        node.arguments.iter().copied(),
+        node.commands.iter().copied(),
+        document.conventions.as_ref(),
+    )
+}
+
+fn render<'a>(
+    command_line: &str,
+    options: impl Iterator<Item = &'a OpenCliOption>,
+    arguments: impl Iterator<Item = &'a OpenCliArgument>,
+    mut commands: impl Iterator<Item = &'a OpenCliCommand>,
+    conventions: Option<&OpenCliConventions>,
+) -> String {
+    let separator = conventions
+        .and_then(|conventions| conventions.option_argument_separator.as_deref())
+        .filter(|separator| !separator.trim().is_empty());
+
+    let mut parts = vec![command_line.to_owned()];
+
+    let mut optional_options = false;
+    let mut required_options = Vec::new();
+    for option in options.filter(|option| !option.hidden.unwrap_or(false)) {
+        if option.required.unwrap_or(false) {
+            required_options.push(option_usage(option, separator));
+        } else {
+            optional_options = true;
+        }
+    }
+    if optional_options {
+        parts.push("[OPTIONS]".to_owned());
+    }
+    parts.extend(required_options);
+
+    let mut after_unbounded = false;
+    let mut separated = false;
+    for argument in arguments.filter(|argument| !argument.hidden.unwrap_or(false)) {
+        let Some(values) = values(argument) else {
+            continue;
+        };
+        if after_unbounded && !separated {
+            // Values following an unbounded argument can only be passed after `--`
+            parts.push("[--]".to_owned());
+            separated = true;
+        }
+        if argument.required.unwrap_or(false) {
+            parts.push(values);
+        } else {
+            parts.push(format!("[{values}]"));
+        }
+        after_unbounded |= value_range(argument).1.is_none();
+    }
+
+    if commands.any(|command| !command.hidden.unwrap_or(false)) {
+        parts.push("<COMMAND>".to_owned());
+    }
+
+    parts.join(" ")
+}
+
+/// The usage of a single option, e.g. `--output <dir>` or `(--output|-o) <dir>`.
+fn option_usage(option: &OpenCliOption, separator: Option<&str>) -> String {
+    let names = if option.aliases.is_empty() {
+        option.name.clone()
+    } else {
+        let names: Vec<&str> = std::iter::once(option.name.as_str())
+            .chain(option.aliases.iter().map(String::as_str))
+            .collect();
+        format!("({})", names.join("|"))
+    };
+
+    let mut usage = names;
+    for (i, argument) in option.arguments.iter().enumerate() {
+        let Some(values) = values(argument) else {
+            continue;
+        };
+        let (minimum, _) = value_range(argument);
+        match separator {
+            Some(separator) if i == 0 && minimum == 0 => {
+                write!(usage, "[{separator}{values}]").unwrap()
+            }
+            Some(separator) if i == 0 => write!(usage, "{separator}{values}").unwrap(),
+            _ if minimum == 0 => write!(usage, " [{values}]").unwrap(),
+            _ => write!(usage, " {values}").unwrap(),
+        }
+    }
+    usage
+}
+
+/// The value placeholder of an argument according to its arity, ignoring optionality.
+///
+/// Returns `None` for arguments which take no values.
+fn values(argument: &OpenCliArgument) -> Option<String> {
+    let value = format!("<{}>", argument.name);
+    let values = match value_range(argument) {
+        (_, Some(0)) => return None,
+        (minimum, Some(1)) if minimum <= 1 => value,
+        (minimum, None) if minimum <= 1 => format!("{value}..."),
+        (minimum, None) => format!("{value}{{{minimum},}}"),
+        (minimum, Some(maximum)) if minimum == maximum => format!("{value}{{{minimum}}}"),
+        (minimum, Some(maximum)) => format!("{value}{{{},{maximum}}}", minimum.max(1)),
+    };
+    Some(values)
+}
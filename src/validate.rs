@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::{
+    OpenCliArgument, OpenCliArity, OpenCliCommand, OpenCliDocument, OpenCliExitCode, OpenCliOption,
+};
+
+/// A semantic violation found while validating an [`OpenCliDocument`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The location of the violation, e.g. `commands[2].options[0].arity`
+    pub path: String,
+
+    /// The kind of violation
+    pub kind: ValidationErrorKind,
+}
+
+/// The kinds of violations reported by [`OpenCliDocument::validate`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationErrorKind {
+    #[error("Required field is empty")]
+    Empty,
+    #[error("Duplicate name `{0}`")]
+    DuplicateName(String),
+    #[error("Duplicate exit code {0}")]
+    DuplicateExitCode(i32),
+    #[error("Arity value {0} is negative")]
+    NegativeArity(i32),
+    #[error("Arity minimum {minimum} is greater than maximum {maximum}")]
+    InvalidArity { minimum: i32, maximum: i32 },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl OpenCliDocument {
+    /// Check the document for semantic errors which are not caught during deserialization.
+    ///
+    /// All violations found in the document tree are returned, each with the path to the offending value.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut validator = Validator::default();
+
+        validator.non_empty("opencli", &self.opencli);
+        validator.non_empty("info.title", &self.info.title);
+        validator.non_empty("info.version", &self.info.version);
+        validator.arguments("", &self.arguments);
+        validator.options("", &self.options);
+        validator.commands("", &self.commands);
+        validator.exit_codes("", &self.exit_codes);
+
+        if validator.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(validator.errors)
+        }
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    errors: Vec<ValidationError>,
+}
+
+impl Validator {
+    fn push(&mut self, path: String, kind: ValidationErrorKind) {
+        self.errors.push(ValidationError { path, kind });
+    }
+
+    fn non_empty(&mut self, path: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.push(path.to_owned(), ValidationErrorKind::Empty);
+        }
+    }
+
+    fn commands(&mut self, prefix: &str, commands: &[OpenCliCommand]) {
+        let mut names = HashSet::new();
+        for (i, command) in commands.iter().enumerate() {
+            let path = join(prefix, &format!("commands[{i}]"));
+            self.non_empty(&join(&path, "name"), &command.name);
+            self.unique(&mut names, &join(&path, "name"), &command.name);
+            for (j, alias) in command.aliases.iter().enumerate() {
+                self.non_empty(&join(&path, &format!("aliases[{j}]")), alias);
+                self.unique(&mut names, &join(&path, &format!("aliases[{j}]")), alias);
+            }
+
+            self.arguments(&path, &command.arguments);
+            self.options(&path, &command.options);
+            self.commands(&path, &command.commands);
+            self.exit_codes(&path, &command.exit_codes);
+        }
+    }
+
+    fn options(&mut self, prefix: &str, options: &[OpenCliOption]) {
+        let mut names = HashSet::new();
+        for (i, option) in options.iter().enumerate() {
+            let path = join(prefix, &format!("options[{i}]"));
+            self.non_empty(&join(&path, "name"), &option.name);
+            self.unique(&mut names, &join(&path, "name"), &option.name);
+            for (j, alias) in option.aliases.iter().enumerate() {
+                self.non_empty(&join(&path, &format!("aliases[{j}]")), alias);
+                self.unique(&mut names, &join(&path, &format!("aliases[{j}]")), alias);
+            }
+
+            self.arguments(&path, &option.arguments);
+        }
+    }
+
+    fn arguments(&mut self, prefix: &str, arguments: &[OpenCliArgument]) {
+        let mut names = HashSet::new();
+        for (i, argument) in arguments.iter().enumerate() {
+            let path = join(prefix, &format!("arguments[{i}]"));
+            self.non_empty(&join(&path, "name"), &argument.name);
+            self.unique(&mut names, &join(&path, "name"), &argument.name);
+            if let Some(arity) = &argument.arity {
+                self.arity(&join(&path, "arity"), arity);
+            }
+        }
+    }
+
+    fn arity(&mut self, path: &str, arity: &OpenCliArity) {
+        for value in [arity.minimum, arity.maximum].into_iter().flatten() {
+            if value < 0 {
+                self.push(path.to_owned(), ValidationErrorKind::NegativeArity(value));
+            }
+        }
+        if let (Some(minimum), Some(maximum)) = (arity.minimum, arity.maximum)
+            && minimum > maximum
+        {
+            self.push(
+                path.to_owned(),
+                ValidationErrorKind::InvalidArity { minimum, maximum },
+            );
+        }
+    }
+
+    fn exit_codes(&mut self, prefix: &str, exit_codes: &[OpenCliExitCode]) {
+        let mut codes = HashSet::new();
+        for (i, exit_code) in exit_codes.iter().enumerate() {
+            if !codes.insert(exit_code.code) {
+                self.push(
+                    join(prefix, &format!("exitCodes[{i}].code")),
+                    ValidationErrorKind::DuplicateExitCode(exit_code.code),
+                );
+            }
+        }
+    }
+
+    fn unique<'a>(&mut self, names: &mut HashSet<&'a str>, path: &str, name: &'a str) {
+        if !name.is_empty() && !names.insert(name) {
+            self.push(
+                path.to_owned(),
+                ValidationErrorKind::DuplicateName(name.to_owned()),
+            );
+        }
+    }
+}
+
+fn join(prefix: &str, segment: &str) -> String {
+    if prefix.is_empty() {
+        segment.to_owned()
+    } else {
+        format!("{prefix}.{segment}")
+    }
+}
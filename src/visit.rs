@@ -0,0 +1,256 @@
+//! Traversal of the document tree.
+//!
+//! [`Visit`] walks a document by reference, [`VisitMut`] by mutable reference. Every hook has
+//! a default implementation which continues the traversal by calling the matching `walk_*`
+//! function, so implementors override only the hooks they care about and call the `walk_*`
+//! function themselves to keep descending.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use opencli::{OpenCliCommand, OpenCliDocument};
+//! use opencli::visit::{self, Context, VisitMut};
+//!
+//! struct StripHidden;
+//!
+//! impl VisitMut for StripHidden {
+//!     fn visit_command_mut(&mut self, context: &Context, command: &mut OpenCliCommand) {
+//!         command.options.retain(|option| !option.hidden.unwrap_or(false));
+//!         visit::walk_command_mut(self, context, command);
+//!     }
+//! }
+//!
+//! let mut opencli = OpenCliDocument::from_path("path/to/opencli.yaml").unwrap();
+//! StripHidden.visit_document_mut(&mut opencli);
+//! ```
+
+use crate::{
+    OpenCliArgument, OpenCliCommand, OpenCliDocument, OpenCliExitCode, OpenCliMetadata,
+    OpenCliOption,
+};
+
+/// The location of a visited item.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Context {
+    /// The names of the commands containing the item, including a visited command itself
+    pub commands: Vec<String>,
+
+    /// The location of the item in the document, e.g. `commands[1].options[0]`
+    pub path: String,
+}
+
+impl Context {
+    fn child(&self, segment: &str) -> Self {
+        let path = if self.path.is_empty() {
+            segment.to_owned()
+        } else {
+            format!("{}.{segment}", self.path)
+        };
+        Context {
+            commands: self.commands.clone(),
+            path,
+        }
+    }
+
+    fn command(&self, index: usize, name: &str) -> Self {
+        let mut context = self.child(&format!("commands[{index}]"));
+        context.commands.push(name.to_owned());
+        context
+    }
+}
+
+/// Read-only traversal of a document.
+pub trait Visit<'a> {
+    /// Visit the document, walking its items with [`walk_document`] by default.
+    fn visit_document(&mut self, document: &'a OpenCliDocument) {
+        walk_document(self, document);
+    }
+
+    /// Visit a command at any depth, walking its items with [`walk_command`] by default.
+    fn visit_command(&mut self, context: &Context, command: &'a OpenCliCommand) {
+        walk_command(self, context, command);
+    }
+
+    /// Visit an option, walking its arguments and metadata with [`walk_option`] by default.
+    fn visit_option(&mut self, context: &Context, option: &'a OpenCliOption) {
+        walk_option(self, context, option);
+    }
+
+    /// Visit a positional or option argument, walking its metadata with [`walk_argument`].
+    fn visit_argument(&mut self, context: &Context, argument: &'a OpenCliArgument) {
+        walk_argument(self, context, argument);
+    }
+
+    /// Visit an exit code of the root or a command.
+    fn visit_exit_code(&mut self, _context: &Context, _exit_code: &'a OpenCliExitCode) {}
+
+    /// Visit a metadata entry of any item.
+    fn visit_metadata(&mut self, _context: &Context, _metadata: &'a OpenCliMetadata) {}
+}
+
+/// Mutable traversal of a document.
+pub trait VisitMut {
+    /// Visit the document, walking its items with [`walk_document_mut`] by default.
+    fn visit_document_mut(&mut self, document: &mut OpenCliDocument) {
+        walk_document_mut(self, document);
+    }
+
+    /// Visit a command at any depth, walking its items with [`walk_command_mut`] by default.
+    fn visit_command_mut(&mut self, context: &Context, command: &mut OpenCliCommand) {
+        walk_command_mut(self, context, command);
+    }
+
+    /// Visit an option, walking its arguments and metadata with [`walk_option_mut`] by default.
+    fn visit_option_mut(&mut self, context: &Context, option: &mut OpenCliOption) {
+        walk_option_mut(self, context, option);
+    }
+
+    /// Visit a positional or option argument, walking its metadata with [`walk_argument_mut`].
+    fn visit_argument_mut(&mut self, context: &Context, argument: &mut OpenCliArgument) {
+        walk_argument_mut(self, context, argument);
+    }
+
+    /// Visit an exit code of the root or a command.
+    fn visit_exit_code_mut(&mut self, _context: &Context, _exit_code: &mut OpenCliExitCode) {}
+
+    /// Visit a metadata entry of any item.
+    fn visit_metadata_mut(&mut self, _context: &Context, _metadata: &mut OpenCliMetadata) {}
+}
+
+/// Visit the root options, arguments, commands, exit codes and metadata of a document.
+pub fn walk_document<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, document: &'a OpenCliDocument) {
+    let context = Context::default();
+    for (i, option) in document.options.iter().enumerate() {
+        visitor.visit_option(&context.child(&format!("options[{i}]")), option);
+    }
+    for (i, argument) in document.arguments.iter().enumerate() {
+        visitor.visit_argument(&context.child(&format!("arguments[{i}]")), argument);
+    }
+    for (i, command) in document.commands.iter().enumerate() {
+        visitor.visit_command(&context.command(i, &command.name), command);
+    }
+    for (i, exit_code) in document.exit_codes.iter().enumerate() {
+        visitor.visit_exit_code(&context.child(&format!("exitCodes[{i}]")), exit_code);
+    }
+    for (i, metadata) in document.metadata.iter().enumerate() {
+        visitor.visit_metadata(&context.child(&format!("metadata[{i}]")), metadata);
+    }
+}
+
+/// Visit the options, arguments, sub commands, exit codes and metadata of a command.
+pub fn walk_command<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    context: &Context,
+    command: &'a OpenCliCommand,
+) {
+    for (i, option) in command.options.iter().enumerate() {
+        visitor.visit_option(&context.child(&format!("options[{i}]")), option);
+    }
+    for (i, argument) in command.arguments.iter().enumerate() {
+        visitor.visit_argument(&context.child(&format!("arguments[{i}]")), argument);
+    }
+    for (i, command) in command.commands.iter().enumerate() {
+        visitor.visit_command(&context.command(i, &command.name), command);
+    }
+    for (i, exit_code) in command.exit_codes.iter().enumerate() {
+        visitor.visit_exit_code(&context.child(&format!("exitCodes[{i}]")), exit_code);
+    }
+    for (i, metadata) in command.metadata.iter().enumerate() {
+        visitor.visit_metadata(&context.child(&format!("metadata[{i}]")), metadata);
+    }
+}
+
+/// Visit the arguments and metadata of an option.
+pub fn walk_option<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    context: &Context,
+    option: &'a OpenCliOption,
+) {
+    for (i, argument) in option.arguments.iter().enumerate() {
+        visitor.visit_argument(&context.child(&format!("arguments[{i}]")), argument);
+    }
+    for (i, metadata) in option.metadata.iter().enumerate() {
+        visitor.visit_metadata(&context.child(&format!("metadata[{i}]")), metadata);
+    }
+}
+
+/// Visit the metadata of an argument.
+pub fn walk_argument<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    context: &Context,
+    argument: &'a OpenCliArgument,
+) {
+    for (i, metadata) in argument.metadata.iter().enumerate() {
+        visitor.visit_metadata(&context.child(&format!("metadata[{i}]")), metadata);
+    }
+}
+
+/// Mutable version of [`walk_document`].
+pub fn walk_document_mut<V: VisitMut + ?Sized>(visitor: &mut V, document: &mut OpenCliDocument) {
+    let context = Context::default();
+    for (i, option) in document.options.iter_mut().enumerate() {
+        visitor.visit_option_mut(&context.child(&format!("options[{i}]")), option);
+    }
+    for (i, argument) in document.arguments.iter_mut().enumerate() {
+        visitor.visit_argument_mut(&context.child(&format!("arguments[{i}]")), argument);
+    }
+    for (i, command) in document.commands.iter_mut().enumerate() {
+        let context = context.command(i, &command.name);
+        visitor.visit_command_mut(&context, command);
+    }
+    for (i, exit_code) in document.exit_codes.iter_mut().enumerate() {
+        visitor.visit_exit_code_mut(&context.child(&format!("exitCodes[{i}]")), exit_code);
+    }
+    for (i, metadata) in document.metadata.iter_mut().enumerate() {
+        visitor.visit_metadata_mut(&context.child(&format!("metadata[{i}]")), metadata);
+    }
+}
+
+/// Mutable version of [`walk_command`].
+pub fn walk_command_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    context: &Context,
+    command: &mut OpenCliCommand,
+) {
+    for (i, option) in command.options.iter_mut().enumerate() {
+        visitor.visit_option_mut(&context.child(&format!("options[{i}]")), option);
+    }
+    for (i, argument) in command.arguments.iter_mut().enumerate() {
+        visitor.visit_argument_mut(&context.child(&format!("arguments[{i}]")), argument);
+    }
+    for (i, command) in command.commands.iter_mut().enumerate() {
+        let context = context.command(i, &command.name);
+        visitor.visit_command_mut(&context, command);
+    }
+    for (i, exit_code) in command.exit_codes.iter_mut().enumerate() {
+        visitor.visit_exit_code_mut(&context.child(&format!("exitCodes[{i}]")), exit_code);
+    }
+    for (i, metadata) in command.metadata.iter_mut().enumerate() {
+        visitor.visit_metadata_mut(&context.child(&format!("metadata[{i}]")), metadata);
+    }
+}
+
+/// Mutable version of [`walk_option`].
+pub fn walk_option_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    context: &Context,
+    option: &mut OpenCliOption,
+) {
+    for (i, argument) in option.arguments.iter_mut().enumerate() {
+        visitor.visit_argument_mut(&context.child(&format!("arguments[{i}]")), argument);
+    }
+    for (i, metadata) in option.metadata.iter_mut().enumerate() {
+        visitor.visit_metadata_mut(&context.child(&format!("metadata[{i}]")), metadata);
+    }
+}
+
+/// Mutable version of [`walk_argument`].
+pub fn walk_argument_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    context: &Context,
+    argument: &mut OpenCliArgument,
+) {
+    for (i, metadata) in argument.metadata.iter_mut().enumerate() {
+        visitor.visit_metadata_mut(&context.child(&format!("metadata[{i}]")), metadata);
+    }
+}
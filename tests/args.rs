@@ -0,0 +1,246 @@
+use opencli::{ArgMatches, ArgsError, ArgsErrorKind, OpenCliDocument};
+
+fn document() -> OpenCliDocument {
+    OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap()
+}
+
+fn parse(document: &OpenCliDocument, args: &[&str]) -> Result<ArgMatches, ArgsError> {
+    document.parse_args(std::iter::once("mytool").chain(args.iter().copied()))
+}
+
+fn kind(document: &OpenCliDocument, args: &[&str]) -> (usize, ArgsErrorKind) {
+    let error = parse(document, args).unwrap_err();
+    (error.index, error.kind)
+}
+
+/// A document with short flags, a valued option and a positional taking any values.
+fn calculator(conventions: &str) -> OpenCliDocument {
+    OpenCliDocument::from_yaml_str(&format!(
+        r#"
+opencli: "0.1"
+info: {{ title: calc, version: "1.0" }}
+{conventions}
+options:
+  - name: -i
+  - name: -n
+  - name: -f
+  - name: -o
+    arguments: [{{ name: file }}]
+arguments:
+  - name: values
+    arity: {{ minimum: 0 }}
+"#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn sub_commands_and_inherited_options() {
+    let document = document();
+    let matches = parse(
+        &document,
+        &["-v", "b", "--color", "never", "-o", "out", "lib", "bin"],
+    )
+    .unwrap();
+    assert_eq!(matches.name, "mytool");
+    assert_eq!(matches.command_path(), ["build"]);
+    assert_eq!(matches.option("--verbose").unwrap().occurrences, 1);
+
+    let build = matches.leaf();
+    assert_eq!(build.value_of("--color"), Some("never"));
+    assert_eq!(build.value_of("--output"), Some("out"));
+    assert_eq!(
+        build.values_of("target"),
+        Some(&["lib".to_owned(), "bin".to_owned()][..])
+    );
+    assert!(!build.is_present("--profile"));
+}
+
+#[test]
+fn attached_values_and_separator() {
+    let document = document();
+    // The document declares a space separator, so values cannot be attached
+    assert_eq!(
+        kind(&document, &["--color=never"]).1,
+        ArgsErrorKind::UnknownOption {
+            name: "--color=never".to_owned(),
+            suggestions: Vec::new()
+        }
+    );
+
+    let calc = calculator("");
+    let matches = parse(&calc, &["-ofile", "-o=other"]).unwrap();
+    assert_eq!(
+        matches.values_of("-o"),
+        Some(&["file".to_owned(), "other".to_owned()][..])
+    );
+}
+
+#[test]
+fn group_options_defaults_to_true() {
+    let matches = parse(&calculator(""), &["-inf", "-no", "out"]).unwrap();
+    for (name, occurrences) in [("-i", 1), ("-n", 2), ("-f", 1), ("-o", 1)] {
+        assert_eq!(matches.option(name).unwrap().occurrences, occurrences);
+    }
+    assert_eq!(matches.value_of("-o"), Some("out"));
+
+    let calc = calculator("conventions: { groupOptions: false }");
+    assert_eq!(
+        kind(&calc, &["-inf"]),
+        (
+            1,
+            ArgsErrorKind::UnknownOption {
+                name: "-inf".to_owned(),
+                suggestions: Vec::new()
+            }
+        )
+    );
+}
+
+#[test]
+fn negative_numbers_are_values() {
+    let calc = calculator("");
+    let matches = parse(&calc, &["-1", "-0.5", "-.5", "-o", "-2", "3"]).unwrap();
+    assert_eq!(
+        matches.values_of("values"),
+        Some(
+            &[
+                "-1".to_owned(),
+                "-0.5".to_owned(),
+                "-.5".to_owned(),
+                "3".to_owned()
+            ][..]
+        )
+    );
+    assert_eq!(matches.value_of("-o"), Some("-2"));
+
+    // Only decimal numerals count, e.g. `-infinity` is a group of flags
+    assert_eq!(
+        kind(&calc, &["-infinity"]),
+        (
+            1,
+            ArgsErrorKind::UnknownOption {
+                name: "-t".to_owned(),
+                suggestions: Vec::new()
+            }
+        )
+    );
+    assert!(parse(&calc, &["-1e5"]).is_err());
+}
+
+#[test]
+fn double_dash_ends_options() {
+    let matches = parse(&calculator(""), &["-i", "--", "-n", "--"]).unwrap();
+    assert!(!matches.is_present("-n"));
+    assert_eq!(
+        matches.values_of("values"),
+        Some(&["-n".to_owned(), "--".to_owned()][..])
+    );
+}
+
+#[test]
+fn errors() {
+    let document = document();
+    assert_eq!(
+        kind(&document, &["--colr"]),
+        (
+            1,
+            ArgsErrorKind::UnknownOption {
+                name: "--colr".to_owned(),
+                suggestions: vec!["--color".to_owned()]
+            }
+        )
+    );
+    assert_eq!(
+        kind(&document, &["build", "lib", "--output"]),
+        (
+            4,
+            ArgsErrorKind::MissingValue {
+                option: "--output".to_owned(),
+                argument: "dir".to_owned()
+            }
+        )
+    );
+    assert_eq!(
+        kind(&document, &["build", "--profile", "fast", "lib"]),
+        (
+            3,
+            ArgsErrorKind::InvalidValue {
+                name: "profile".to_owned(),
+                value: "fast".to_owned(),
+                accepted_values: vec!["debug".to_owned(), "release".to_owned()]
+            }
+        )
+    );
+    assert_eq!(
+        kind(&document, &["remote", "add", "origin"]),
+        (4, ArgsErrorKind::MissingArgument("url".to_owned()))
+    );
+    assert_eq!(
+        kind(&document, &["remote", "rm", "origin"]),
+        (3, ArgsErrorKind::UnexpectedArgument("origin".to_owned()))
+    );
+}
+
+#[test]
+fn error_display() {
+    let error = parse(&document(), &["--colr"]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "argv[1]: Unknown option `--colr`, did you mean `--color`?"
+    );
+}
+
+fn exit_code(yaml: &str, args: &[&str]) -> Option<i32> {
+    let document = OpenCliDocument::from_yaml_str(yaml).unwrap();
+    parse(&document, args)
+        .unwrap_err()
+        .exit_code
+        .map(|exit_code| exit_code.code)
+}
+
+#[test]
+fn exit_code_by_description() {
+    let yaml = r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+exitCodes:
+  - { code: 1, description: General failure }
+  - { code: 2, description: Reserved }
+  - { code: 3, description: Invalid Option or ARGUMENT }
+commands:
+  - name: build
+    exitCodes:
+      - { code: 10, description: Build failed }
+      - { code: 11, description: Bad usage of build }
+  - name: test
+    exitCodes:
+      - { code: 20, description: Tests failed }
+"#;
+    // Descriptions mentioning usage errors win over the conventional codes, regardless of case
+    assert_eq!(exit_code(yaml, &["--unknown"]), Some(3));
+    // The innermost matched command is searched first
+    assert_eq!(exit_code(yaml, &["build", "--unknown"]), Some(11));
+    // Commands without a fitting exit code fall back to their parents
+    assert_eq!(exit_code(yaml, &["test", "--unknown"]), Some(3));
+}
+
+#[test]
+fn exit_code_by_convention() {
+    let yaml = r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+exitCodes:
+  - { code: 1, description: General failure }
+  - { code: 64 }
+"#;
+    assert_eq!(exit_code(yaml, &["--unknown"]), Some(64));
+
+    let yaml = r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+exitCodes:
+  - { code: 1, description: General failure }
+"#;
+    assert_eq!(exit_code(yaml, &["--unknown"]), None);
+}
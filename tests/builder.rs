@@ -0,0 +1,113 @@
+use opencli::{
+    OpenCliArgument, OpenCliArity, OpenCliCommand, OpenCliDocument, OpenCliExitCode,
+    OpenCliMetadata, OpenCliOption, ValidationErrorKind,
+};
+
+#[test]
+fn build_document() {
+    let document = OpenCliDocument::builder("mytool", "1.0")
+        .summary("A sample tool")
+        .option(
+            OpenCliOption::flag("--verbose")
+                .alias("-v")
+                .recursive(true)
+                .description("Verbose output"),
+        )
+        .command(
+            OpenCliCommand::new("build")
+                .alias("b")
+                .description("Build the project")
+                .option(
+                    OpenCliOption::new("--output")
+                        .alias("-o")
+                        .group("Output")
+                        .argument(OpenCliArgument::new("dir").required(true)),
+                )
+                .argument(
+                    OpenCliArgument::new("target")
+                        .required(true)
+                        .arity(OpenCliArity::at_least(1))
+                        .accepted_values(["lib", "bin"]),
+                )
+                .exit_code(OpenCliExitCode::new(1, "Build failed"))
+                .metadata(OpenCliMetadata::new("owner", "build-team")),
+        )
+        .exit_code(OpenCliExitCode::new(0, "Success"))
+        .example("mytool build lib")
+        .build()
+        .unwrap();
+
+    let yaml = r#"
+opencli: "0.1"
+info:
+  title: mytool
+  version: "1.0"
+  summary: A sample tool
+options:
+  - name: --verbose
+    aliases: [-v]
+    description: Verbose output
+    recursive: true
+commands:
+  - name: build
+    aliases: [b]
+    description: Build the project
+    options:
+      - name: --output
+        aliases: [-o]
+        group: Output
+        arguments: [{ name: dir, required: true }]
+    arguments:
+      - name: target
+        required: true
+        arity: { minimum: 1 }
+        acceptedValues: [lib, bin]
+    exitCodes: [{ code: 1, description: Build failed }]
+    metadata: [{ name: owner, value: build-team }]
+exitCodes: [{ code: 0, description: Success }]
+examples: [mytool build lib]
+"#;
+    assert_eq!(document, OpenCliDocument::from_yaml_str(yaml).unwrap());
+}
+
+#[test]
+fn flag() {
+    let option = OpenCliOption::flag("--verbose").alias("-v");
+    assert_eq!(option.name, "--verbose");
+    assert_eq!(option.aliases, ["-v"]);
+    assert!(option.arguments.is_empty());
+    assert_eq!(option, OpenCliOption::new("--verbose").alias("-v"));
+}
+
+#[test]
+fn arity_constructors() {
+    let range = |arity: OpenCliArity| (arity.minimum, arity.maximum);
+    assert_eq!(range(OpenCliArity::exactly(2)), (Some(2), Some(2)));
+    assert_eq!(range(OpenCliArity::at_least(1)), (Some(1), None));
+    assert_eq!(range(OpenCliArity::at_most(3)), (Some(0), Some(3)));
+    assert_eq!(range(OpenCliArity::between(1, 4)), (Some(1), Some(4)));
+}
+
+#[test]
+fn build_validates() {
+    let errors = OpenCliDocument::builder("mytool", "")
+        .command(OpenCliCommand::new("build"))
+        .command(OpenCliCommand::new("b").hidden(true))
+        .command(OpenCliCommand::new("test").alias("b"))
+        .build()
+        .unwrap_err();
+    let errors: Vec<(&str, &ValidationErrorKind)> = errors
+        .iter()
+        .map(|error| (error.path.as_str(), &error.kind))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            ("info.version", &ValidationErrorKind::Empty),
+            (
+                "commands[2].aliases[0]",
+                &ValidationErrorKind::DuplicateName("b".to_owned())
+            ),
+        ]
+    );
+}
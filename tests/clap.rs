@@ -0,0 +1,246 @@
+#![cfg(feature = "clap")]
+
+use clap::{Arg, ArgAction, Command};
+use opencli::{OpenCliArgument, OpenCliArity, OpenCliDocument, OpenCliOption};
+
+fn command() -> Command {
+    Command::new("mytool")
+        .version("1.2.3")
+        .about("A sample tool")
+        .arg(
+            Arg::new("verbose")
+                .long("verbose")
+                .short('v')
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help("Verbose output"),
+        )
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .value_name("WHEN")
+                .value_parser(["auto", "always", "never"]),
+        )
+        .subcommand(
+            Command::new("build")
+                .visible_alias("b")
+                .about("Build the project")
+                .arg(
+                    Arg::new("output")
+                        .long("output")
+                        .short('o')
+                        .visible_alias("out")
+                        .value_name("DIR")
+                        .required(true)
+                        .help_heading("Output"),
+                )
+                .arg(
+                    Arg::new("define")
+                        .long("define")
+                        .short('D')
+                        .value_names(["KEY", "VALUE"])
+                        .num_args(2),
+                )
+                .arg(Arg::new("target").required(true).num_args(1..))
+                .arg(Arg::new("secret").long("secret").hide(true)),
+        )
+        .subcommand(Command::new("internal").hide(true))
+}
+
+#[test]
+fn from_clap() {
+    let document = OpenCliDocument::from_clap(&command());
+    assert_eq!(document.info.title, "mytool");
+    assert_eq!(document.info.version, "1.2.3");
+    assert_eq!(document.info.summary.as_deref(), Some("A sample tool"));
+
+    assert_eq!(
+        document.options[0],
+        OpenCliOption {
+            name: "--verbose".to_owned(),
+            aliases: vec!["-v".to_owned()],
+            description: Some("Verbose output".to_owned()),
+            recursive: Some(true),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        document.options[1].arguments,
+        [OpenCliArgument {
+            name: "WHEN".to_owned(),
+            required: Some(true),
+            accepted_values: vec!["auto".to_owned(), "always".to_owned(), "never".to_owned()],
+            ..Default::default()
+        }]
+    );
+
+    let build = &document.commands[0];
+    assert_eq!(build.name, "build");
+    assert_eq!(build.aliases, ["b"]);
+    assert_eq!(build.description.as_deref(), Some("Build the project"));
+    let output = &build.options[0];
+    assert_eq!(output.name, "--output");
+    assert_eq!(output.aliases, ["-o", "--out"]);
+    assert_eq!(output.required, Some(true));
+    assert_eq!(output.group.as_deref(), Some("Output"));
+    // Several value names with a matching count become one argument each
+    let define: Vec<&str> = build.options[1]
+        .arguments
+        .iter()
+        .map(|argument| argument.name.as_str())
+        .collect();
+    assert_eq!(define, ["KEY", "VALUE"]);
+    assert_eq!(build.options[2].hidden, Some(true));
+    assert_eq!(
+        build.arguments,
+        [OpenCliArgument {
+            name: "target".to_owned(),
+            required: Some(true),
+            arity: Some(OpenCliArity {
+                minimum: Some(1),
+                maximum: None,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }]
+    );
+
+    assert_eq!(document.commands[1].hidden, Some(true));
+    // Flags clap adds while building are not part of the command
+    assert!(
+        !document
+            .options
+            .iter()
+            .any(|option| option.name == "--help" || option.name == "--version")
+    );
+    assert_eq!(document.validate(), Ok(()));
+}
+
+#[test]
+fn from_clap_to_clap_roundtrip() {
+    let document = OpenCliDocument::from_clap(&command());
+    let (command, incompatibilities) = document.to_clap();
+    assert_eq!(incompatibilities, []);
+    assert_eq!(OpenCliDocument::from_clap(&command), document);
+}
+
+#[test]
+fn to_clap_parses_argv() {
+    let document = OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap();
+    let (command, incompatibilities) = document.to_clap();
+    let paths: Vec<&str> = incompatibilities
+        .iter()
+        .map(|incompatibility| incompatibility.path.as_str())
+        .collect();
+    assert_eq!(paths, ["exitCodes"]);
+
+    let matches = command
+        .clone()
+        .try_get_matches_from([
+            "mytool", "b", "-v", "--color", "never", "-o", "out", "lib", "bin",
+        ])
+        .unwrap();
+    let (name, build) = matches.subcommand().unwrap();
+    assert_eq!(name, "build");
+    assert!(build.get_flag("verbose"));
+    assert_eq!(build.get_one::<String>("color").unwrap(), "never");
+    assert_eq!(build.get_one::<String>("output").unwrap(), "out");
+    let targets: Vec<&String> = build.get_many("target").unwrap().collect();
+    assert_eq!(targets, ["lib", "bin"]);
+
+    // Accepted values and required arguments are enforced
+    let error = command
+        .clone()
+        .try_get_matches_from(["mytool", "build", "-o", "out", "exe"])
+        .unwrap_err();
+    assert_eq!(error.kind(), clap::error::ErrorKind::InvalidValue);
+    let error = command
+        .try_get_matches_from(["mytool", "remote", "add", "origin"])
+        .unwrap_err();
+    assert_eq!(
+        error.kind(),
+        clap::error::ErrorKind::MissingRequiredArgument
+    );
+}
+
+#[test]
+fn to_clap_separates_option_and_argument_ids() {
+    let document = OpenCliDocument::from_yaml_str(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+options:
+  - name: --name
+    recursive: true
+    arguments: [{ name: value }]
+commands:
+  - name: deploy
+    options:
+      - name: --target
+        arguments: [{ name: value }]
+    arguments:
+      - name: target
+      - name: name
+"#,
+    )
+    .unwrap();
+    let (command, incompatibilities) = document.to_clap();
+    assert_eq!(
+        incompatibilities
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        [
+            "options[0].name: Option and argument share the identifier `name`, the option is identified by its full name",
+            "commands[0].options[0].name: Option and argument share the identifier `target`, the option is identified by its full name",
+        ]
+    );
+
+    let matches = command
+        .try_get_matches_from([
+            "mytool", "deploy", "--target", "prod", "--name", "web", "eu", "app",
+        ])
+        .unwrap();
+    let deploy = matches.subcommand_matches("deploy").unwrap();
+    assert_eq!(deploy.get_one::<String>("--target").unwrap(), "prod");
+    assert_eq!(deploy.get_one::<String>("--name").unwrap(), "web");
+    assert_eq!(deploy.get_one::<String>("target").unwrap(), "eu");
+    assert_eq!(deploy.get_one::<String>("name").unwrap(), "app");
+}
+
+#[test]
+fn to_clap_reports_incompatibilities() {
+    let document = OpenCliDocument::from_yaml_str(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+conventions: { groupOptions: false, optionArgumentSeparator: ":" }
+options:
+  - name: --verbose
+    recursive: true
+  - name: /help
+commands:
+  - name: build
+    options: [{ name: --verbose }]
+    arguments:
+      - { name: files, arity: { minimum: 1 } }
+      - { name: output }
+      - { name: extra }
+"#,
+    )
+    .unwrap();
+    let (_, incompatibilities) = document.to_clap();
+    assert_eq!(
+        incompatibilities
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        [
+            "conventions.groupOptions: Short options can always be grouped",
+            "conventions.optionArgumentSeparator: Option argument separator `:` is not supported",
+            "options[1].name: Option name `/help` is neither a long `--name` nor a short `-n` name",
+            "commands[0].options[0]: Option `--verbose` shadows a recursive option",
+            "commands[0].arguments[1]: Argument `output` follows an unbounded argument which is not the last one",
+        ]
+    );
+}
@@ -0,0 +1,165 @@
+use std::{fs, path::Path};
+
+use opencli::{
+    OpenCliDocument,
+    completion::{self, Shell},
+};
+
+/// Compare the generated script with the stored snapshot.
+///
+/// Run with `UPDATE_SNAPSHOTS=1` to write the snapshots instead.
+fn assert_snapshot(shell: Shell, file: &str) {
+    let document = OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap();
+    let script = completion::generate(&document, shell);
+
+    let path = Path::new("tests/snapshots").join(file);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, &script).unwrap();
+    }
+    let snapshot = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        script,
+        snapshot,
+        "snapshot `{}` does not match",
+        path.display()
+    );
+}
+
+#[test]
+fn bash() {
+    assert_snapshot(Shell::Bash, "mytool.bash");
+}
+
+#[test]
+fn zsh() {
+    assert_snapshot(Shell::Zsh, "mytool.zsh");
+}
+
+#[test]
+fn fish() {
+    assert_snapshot(Shell::Fish, "mytool.fish");
+}
+
+#[test]
+fn powershell() {
+    assert_snapshot(Shell::PowerShell, "mytool.ps1");
+}
+
+/// Run the generated bash completion function for the given words, the last one being completed.
+#[cfg(unix)]
+fn bash_complete(script: &str, words: &[&str]) -> Vec<String> {
+    let words: Vec<String> = words.iter().map(|word| format!("'{word}'")).collect();
+    let command = format!(
+        "{script}\nCOMP_WORDS=({})\nCOMP_CWORD={}\n_mytool\nprintf '%s\\n' \"${{COMPREPLY[@]}}\"",
+        words.join(" "),
+        words.len() - 1
+    );
+    let output = std::process::Command::new("bash")
+        .args(["--norc", "-c", &command])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+#[test]
+#[cfg(unix)]
+fn bash_completes_words() {
+    let document = OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap();
+    let script = completion::generate(&document, Shell::Bash);
+
+    assert_eq!(
+        bash_complete(&script, &["mytool", ""]),
+        ["build", "b", "remote"]
+    );
+    assert_eq!(bash_complete(&script, &["mytool", "re"]), ["remote"]);
+    assert_eq!(bash_complete(&script, &["mytool", "--c"]), ["--color"]);
+    assert_eq!(
+        bash_complete(&script, &["mytool", "--color", "a"]),
+        ["auto", "always"]
+    );
+    // Aliases select the command, and recursive options are inherited
+    assert_eq!(
+        bash_complete(&script, &["mytool", "b", "--profile", ""]),
+        ["debug", "release"]
+    );
+    assert_eq!(
+        bash_complete(&script, &["mytool", "b", "--v"]),
+        ["--verbose"]
+    );
+    assert_eq!(
+        bash_complete(&script, &["mytool", "remote", "r"]),
+        ["remove", "rm"]
+    );
+    // Hidden commands and options are not offered
+    assert!(bash_complete(&script, &["mytool", "i"]).is_empty());
+    assert!(bash_complete(&script, &["mytool", "--s"]).is_empty());
+}
+
+#[test]
+fn zsh_describes_arguments_by_name() {
+    let document = OpenCliDocument::from_yaml_str(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+options:
+  - name: --jobs
+    aliases: [-j]
+    description: "Number of jobs: defaults to 1"
+    arguments: [{ name: count }]
+arguments:
+  - name: source
+    group: Files
+    required: true
+  - name: destination
+    group: Files
+  - name: extra
+    arity: { minimum: 0 }
+    acceptedValues: [a, "b c"]
+"#,
+    )
+    .unwrap();
+    let script = completion::generate(&document, Shell::Zsh);
+
+    assert!(script.starts_with("#compdef mytool\n"));
+    assert!(script.contains("'(--jobs -j)--jobs[Number of jobs\\: defaults to 1]:count:_default'"));
+    assert!(script.contains("'(--jobs -j)-j[Number of jobs\\: defaults to 1]:count:_default'"));
+    assert!(script.contains("':source:_default'"));
+    assert!(script.contains("'::destination:_default'"));
+    assert!(script.contains("'*::extra:(a b\\ c)'"));
+    assert!(!script.contains("Files"));
+}
+
+#[test]
+fn zsh_follows_group_options() {
+    let document = |conventions: &str| {
+        OpenCliDocument::from_yaml_str(&format!(
+            "opencli: \"0.1\"\ninfo: {{ title: mytool, version: \"1.0\" }}\n{conventions}"
+        ))
+        .unwrap()
+    };
+    let script = completion::generate(&document(""), Shell::Zsh);
+    assert!(script.contains("    _arguments -C -s \\\n"));
+    let script = completion::generate(
+        &document("conventions: { groupOptions: false }"),
+        Shell::Zsh,
+    );
+    assert!(script.contains("    _arguments -C \\\n"));
+    assert!(!script.contains("-s"));
+}
+
+#[test]
+fn zsh_quotes_program_name() {
+    let document = OpenCliDocument::from_yaml_str(
+        "opencli: \"0.1\"\ninfo: { title: \"my tool's\", version: \"1.0\" }",
+    )
+    .unwrap();
+    let script = completion::generate(&document, Shell::Zsh);
+    assert!(script.starts_with("#compdef 'my tool'\\''s'\n"));
+    assert!(script.contains("    compdef _my_tool_s 'my tool'\\''s'\n"));
+}
@@ -0,0 +1,323 @@
+use opencli::OpenCliDocument;
+use opencli::diff::{ArityDirection, Bump, ChangeKind, Item, Severity, VersionError};
+
+fn document(version: &str, body: &str) -> OpenCliDocument {
+    OpenCliDocument::from_yaml_str(&format!(
+        "opencli: \"0.1\"\ninfo: {{ title: mytool, version: \"{version}\" }}\n{body}"
+    ))
+    .unwrap()
+}
+
+/// The changes between two document bodies, as display strings.
+fn changes(old: &str, new: &str) -> Vec<String> {
+    opencli::diff(&document("1.0.0", old), &document("1.0.0", new))
+        .changes
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn identical_documents() {
+    let document = OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap();
+    let diff = opencli::diff(&document, &document);
+    assert!(diff.is_empty());
+    assert_eq!(diff.severity(), None);
+    assert_eq!(diff.required_bump(), Bump::None);
+}
+
+#[test]
+fn reordering_is_not_a_change() {
+    assert_eq!(
+        changes(
+            "options: [{ name: --a }, { name: --b }]\ncommands: [{ name: x }, { name: y }]",
+            "options: [{ name: --b }, { name: --a }]\ncommands: [{ name: y }, { name: x }]",
+        ),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn moved_arguments_are_breaking() {
+    assert_eq!(
+        changes(
+            "arguments: [{ name: src }, { name: dst }]",
+            "arguments: [{ name: dst }, { name: src }]",
+        ),
+        [
+            "[breaking] <root>: argument `src` moved from position 1 to 2",
+            "[breaking] <root>: argument `dst` moved from position 2 to 1",
+        ]
+    );
+    assert_eq!(
+        changes(
+            "options: [{ name: --define, arguments: [{ name: key }, { name: value }] }]",
+            "options: [{ name: --define, arguments: [{ name: value }, { name: key }] }]",
+        ),
+        [
+            "[breaking] <root>: option `--define` argument `key` moved from position 1 to 2",
+            "[breaking] <root>: option `--define` argument `value` moved from position 2 to 1",
+        ]
+    );
+}
+
+#[test]
+fn conventions_use_defaults() {
+    // Explicit defaults are no change
+    assert_eq!(
+        changes(
+            "",
+            "conventions: { groupOptions: true, optionArgumentSeparator: \" \" }"
+        ),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        changes(
+            "conventions: { groupOptions: true }",
+            "conventions: { groupOptions: false, optionArgumentSeparator: \"=\" }",
+        ),
+        [
+            "[breaking] <root>: conventions short option grouping disabled",
+            "[breaking] <root>: conventions option argument separator changed from ` ` to `=`",
+        ]
+    );
+}
+
+#[test]
+fn severity_classification() {
+    let old = document(
+        "1.0.0",
+        r#"
+options:
+  - name: --output
+    aliases: [-o]
+    arguments: [{ name: dir, arity: { minimum: 1, maximum: 2 }, acceptedValues: [a, b] }]
+  - name: --quiet
+  - name: --color
+    aliases: [--colour]
+arguments: [{ name: file }]
+commands:
+  - name: build
+    description: Build it
+exitCodes: [{ code: 1 }]
+"#,
+    );
+    let new = document(
+        "1.0.0",
+        r#"
+options:
+  - name: --output
+    aliases: [-O]
+    arguments: [{ name: dir, arity: { minimum: 1, maximum: 3 }, acceptedValues: [a, c] }]
+  - name: --colour
+    aliases: [--color]
+  - name: --jobs
+  - name: --token
+    required: true
+arguments: [{ name: file, required: true }]
+commands:
+  - name: build
+    description: Build the project
+exitCodes: [{ code: 1 }, { code: 2 }]
+"#,
+    );
+    let diff = opencli::diff(&old, &new);
+    let changes: Vec<(Item, ChangeKind, Severity)> = diff
+        .changes
+        .iter()
+        .map(|change| (change.item.clone(), change.kind.clone(), change.severity))
+        .collect();
+    let option = |name: &str| Item::Option {
+        name: name.to_owned(),
+    };
+    let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+    assert_eq!(
+        changes,
+        [
+            (option("--quiet"), ChangeKind::Removed, Severity::Breaking),
+            (option("--jobs"), ChangeKind::Added, Severity::NonBreaking),
+            (option("--token"), ChangeKind::Added, Severity::Breaking),
+            (
+                option("--output"),
+                ChangeKind::AliasRemoved {
+                    alias: "-o".to_owned()
+                },
+                Severity::Breaking
+            ),
+            (
+                option("--output"),
+                ChangeKind::AliasAdded {
+                    alias: "-O".to_owned()
+                },
+                Severity::NonBreaking
+            ),
+            (
+                Item::OptionArgument {
+                    option: "--output".to_owned(),
+                    argument: "dir".to_owned()
+                },
+                ChangeKind::ArityChanged {
+                    from: old.options[0].arguments[0].arity.clone(),
+                    to: new.options[0].arguments[0].arity.clone(),
+                    direction: ArityDirection::Widened
+                },
+                Severity::NonBreaking
+            ),
+            (
+                Item::OptionArgument {
+                    option: "--output".to_owned(),
+                    argument: "dir".to_owned()
+                },
+                ChangeKind::AcceptedValuesChanged {
+                    added: strings(&["c"]),
+                    removed: strings(&["b"])
+                },
+                Severity::Breaking
+            ),
+            // Keeping the old name as an alias keeps invocations working
+            (
+                option("--colour"),
+                ChangeKind::Renamed {
+                    from: "--color".to_owned(),
+                    to: "--colour".to_owned()
+                },
+                Severity::NonBreaking
+            ),
+            (
+                Item::Argument {
+                    name: "file".to_owned()
+                },
+                ChangeKind::RequiredChanged {
+                    from: false,
+                    to: true
+                },
+                Severity::Breaking
+            ),
+            (
+                Item::ExitCode { code: 2 },
+                ChangeKind::Added,
+                Severity::NonBreaking
+            ),
+            (
+                Item::Command,
+                ChangeKind::DescriptionChanged {
+                    from: Some("Build it".to_owned()),
+                    to: Some("Build the project".to_owned())
+                },
+                Severity::Informational
+            ),
+        ]
+    );
+    assert_eq!(diff.severity(), Some(Severity::Breaking));
+    assert_eq!(diff.changes_with(Severity::Informational).count(), 1);
+    assert_eq!(diff.changes.last().unwrap().command, ["build"]);
+}
+
+#[test]
+fn narrowed_and_shifted_arity() {
+    assert_eq!(
+        changes(
+            "arguments: [{ name: a, arity: { minimum: 0 } }, { name: b, arity: { minimum: 1, maximum: 2 } }]",
+            "arguments: [{ name: a, arity: { minimum: 0, maximum: 3 } }, { name: b, arity: { minimum: 3, maximum: 4 } }]",
+        ),
+        [
+            "[breaking] <root>: argument `a` arity narrowed from 0.. to 0..3",
+            "[breaking] <root>: argument `b` arity changed from 1..2 to 3..4",
+        ]
+    );
+}
+
+fn check(old: &str, new: &str, body_old: &str, body_new: &str) -> Result<(), VersionError> {
+    opencli::diff(&document(old, body_old), &document(new, body_new)).check_version()
+}
+
+#[test]
+fn required_bumps() {
+    let breaking = ("options: [{ name: --a }]", "");
+    let feature = ("", "options: [{ name: --a }]");
+    let docs = (
+        "commands: [{ name: x }]",
+        "commands: [{ name: x, description: X }]",
+    );
+
+    assert_eq!(check("1.2.3", "2.0.0", breaking.0, breaking.1), Ok(()));
+    assert_eq!(check("1.2.3", "1.3.0", feature.0, feature.1), Ok(()));
+    assert_eq!(check("1.2.3", "1.2.4", docs.0, docs.1), Ok(()));
+    assert_eq!(check("1.2.3", "1.2.3", "", ""), Ok(()));
+    assert_eq!(
+        check("1.2.3", "1.3.0", breaking.0, breaking.1),
+        Err(VersionError::TooSmall {
+            old: "1.2.3".to_owned(),
+            new: "1.3.0".to_owned(),
+            actual: Bump::Minor,
+            required: Bump::Major
+        })
+    );
+    assert_eq!(
+        check("1.2.3", "1.2.4", feature.0, feature.1)
+            .unwrap_err()
+            .to_string(),
+        "Version bump from `1.2.3` to `1.2.4` is too small, the changes require a minor bump"
+    );
+}
+
+#[test]
+fn zero_major_bumps() {
+    let breaking = ("options: [{ name: --a }]", "");
+    let feature = ("", "options: [{ name: --a }]");
+
+    // A minor bump of 0.x is major, a patch bump minor
+    assert_eq!(check("0.3.1", "0.4.0", breaking.0, breaking.1), Ok(()));
+    assert_eq!(check("0.3.1", "0.3.2", feature.0, feature.1), Ok(()));
+    assert!(check("0.3.1", "0.3.2", breaking.0, breaking.1).is_err());
+    // Any bump of 0.0.x is major
+    assert_eq!(check("0.0.1", "0.0.2", breaking.0, breaking.1), Ok(()));
+    assert_eq!(
+        check("v0.0.1", "0.1.0-beta+1", breaking.0, breaking.1),
+        Ok(())
+    );
+    // Missing parts count as 0, going back is no bump
+    assert_eq!(check("1", "1.1", feature.0, feature.1), Ok(()));
+    assert!(check("1.1.0", "1.0.9", "", "options: [{ name: --a }]").is_err());
+}
+
+#[test]
+fn invalid_versions() {
+    assert_eq!(
+        check("1.x", "2.0.0", "", ""),
+        Err(VersionError::Invalid("1.x".to_owned()))
+    );
+    assert_eq!(
+        check("1.0.0", "1.0.0.0", "", ""),
+        Err(VersionError::Invalid("1.0.0.0".to_owned()))
+    );
+}
+
+#[test]
+fn recursive_options() {
+    assert_eq!(
+        changes(
+            "options: [{ name: --verbose, recursive: true }, { name: --color }]",
+            "options: [{ name: --verbose }, { name: --color, recursive: true }]",
+        ),
+        [
+            "[breaking] <root>: option `--verbose` made non-recursive",
+            "[non-breaking] <root>: option `--color` made recursive",
+        ]
+    );
+}
+
+#[test]
+fn hidden_items() {
+    assert_eq!(
+        changes(
+            "options: [{ name: --secret }]\narguments: [{ name: file, hidden: true }]\ncommands: [{ name: internal }]",
+            "options: [{ name: --secret, hidden: true }]\narguments: [{ name: file }]\ncommands: [{ name: internal, hidden: true }]",
+        ),
+        [
+            "[informational] <root>: option `--secret` hidden",
+            "[informational] <root>: argument `file` unhidden",
+            "[informational] internal: command hidden",
+        ]
+    );
+}
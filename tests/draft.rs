@@ -0,0 +1,165 @@
+use opencli::{Error, OpenCliDocument, SchemaError, SchemaErrorKind};
+use serde_json::json;
+
+const DOCUMENT: &str = r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0.0", x-team: core }
+commands:
+  - name: build
+    decsription: Build the project
+    x-owner: me
+    options:
+      - name: --jobs
+        arguments: [{ name: count, arity: { minimum: -1 } }]
+"#;
+
+fn error(pointer: &str, kind: SchemaErrorKind) -> SchemaError {
+    SchemaError {
+        pointer: pointer.to_owned(),
+        kind,
+    }
+}
+
+#[test]
+fn fixture_is_valid() {
+    let content = std::fs::read_to_string("tests/data/mytool.yaml").unwrap();
+    let value: serde_json::Value = serde_yaml::from_str(&content).unwrap();
+    assert_eq!(OpenCliDocument::validate_schema(&value, true), Ok(()));
+    assert!(OpenCliDocument::from_path_checked("tests/data/mytool.yaml", true).is_ok());
+}
+
+#[test]
+fn strict_mode_reports_unknown_fields() {
+    let value: serde_json::Value = serde_yaml::from_str(DOCUMENT).unwrap();
+    assert_eq!(
+        OpenCliDocument::validate_schema(&value, true),
+        Err(vec![
+            error(
+                "/commands/0/decsription",
+                SchemaErrorKind::UnknownField("decsription".to_owned())
+            ),
+            error(
+                "/commands/0/options/0/arguments/0/arity/minimum",
+                SchemaErrorKind::Minimum {
+                    value: -1.0,
+                    minimum: 0.0
+                }
+            ),
+        ])
+    );
+}
+
+#[test]
+fn lenient_mode_ignores_unknown_fields() {
+    let value: serde_json::Value = serde_yaml::from_str(DOCUMENT).unwrap();
+    assert_eq!(
+        OpenCliDocument::validate_schema(&value, false),
+        Err(vec![error(
+            "/commands/0/options/0/arguments/0/arity/minimum",
+            SchemaErrorKind::Minimum {
+                value: -1.0,
+                minimum: 0.0
+            }
+        )])
+    );
+
+    let valid = DOCUMENT.replace("minimum: -1", "minimum: 1");
+    let document = OpenCliDocument::from_str_checked(&valid, false).unwrap();
+    // The unknown field is kept like an extension
+    assert_eq!(
+        document.commands[0].extensions["decsription"],
+        json!("Build the project")
+    );
+    assert!(matches!(
+        OpenCliDocument::from_str_checked(&valid, true),
+        Err(Error::Schema(errors)) if errors.len() == 1
+    ));
+}
+
+#[test]
+fn type_and_required_errors() {
+    let value = json!({
+        "opencli": "0.1",
+        "info": { "title": "mytool" },
+        "commands": [{ "name": "build", "hidden": "yes", "aliases": ["b", "b"] }],
+    });
+    assert_eq!(
+        OpenCliDocument::validate_schema(&value, true),
+        Err(vec![
+            error(
+                "/commands/0/aliases/1",
+                SchemaErrorKind::Duplicate(json!("b"))
+            ),
+            error(
+                "/commands/0/hidden",
+                SchemaErrorKind::Type("a boolean".to_owned())
+            ),
+            error("/info", SchemaErrorKind::MissingField("version".to_owned())),
+        ])
+    );
+}
+
+#[test]
+fn schema_error_display() {
+    let error = OpenCliDocument::from_str_checked(DOCUMENT, true).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Document does not match the OpenCLI schema, 2 errors, the first: \
+         /commands/0/decsription: Unknown field `decsription`"
+    );
+    let error = OpenCliDocument::from_str_checked(DOCUMENT, false).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Document does not match the OpenCLI schema, 1 error: \
+         /commands/0/options/0/arguments/0/arity/minimum: Value -1 is less than the minimum 0"
+    );
+}
+
+#[test]
+fn reader_and_path_variants() {
+    assert!(matches!(
+        OpenCliDocument::from_reader_checked(DOCUMENT.as_bytes(), false),
+        Err(Error::Schema(errors)) if errors.len() == 1
+    ));
+    let valid = DOCUMENT.replace("minimum: -1", "minimum: 1");
+    assert!(OpenCliDocument::from_reader_checked(valid.as_bytes(), false).is_ok());
+
+    // Includes are resolved before checking, a `$ref` is not an unknown field
+    let document = OpenCliDocument::from_path_checked("tests/data/include/main.yaml", true);
+    assert_eq!(
+        document.unwrap(),
+        OpenCliDocument::from_path("tests/data/include/main.yaml").unwrap()
+    );
+    assert!(matches!(
+        OpenCliDocument::from_path_checked("tests/data/missing.yaml", false),
+        Err(Error::Io(_))
+    ));
+}
+
+#[test]
+fn checked_parsing_falls_back_to_the_other_format() {
+    // Detected as JSON, but only valid YAML
+    let content = r#"{ opencli: "0.1", info: { title: mytool, version: "1.0.0" } }"#;
+    assert_eq!(
+        OpenCliDocument::from_str_checked(content, true).unwrap(),
+        content.parse::<OpenCliDocument>().unwrap()
+    );
+}
+
+#[test]
+fn checked_parsing_reports_locations() {
+    // Out of range for the arity, but a valid schema integer
+    let content = "opencli: \"0.1\"\ninfo: { title: mytool, version: \"1.0.0\" }\ncommands:\n  - name: build\n    arguments: [{ name: target, arity: { minimum: 3000000000 } }]\n";
+    let Error::Parse { error, .. } = OpenCliDocument::from_str_checked(content, false).unwrap_err()
+    else {
+        panic!("Expected a parse error");
+    };
+    let Error::Parse {
+        error: expected, ..
+    } = content.parse::<OpenCliDocument>().unwrap_err()
+    else {
+        panic!("Expected a parse error");
+    };
+    assert_eq!(error, expected);
+    assert_eq!(error.line, Some(5));
+}
@@ -0,0 +1,99 @@
+use opencli::OpenCliDocument;
+
+fn document() -> OpenCliDocument {
+    OpenCliDocument::from_yaml_str(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+options:
+  - name: --verbose
+    aliases: [-v]
+    recursive: true
+  - name: --color
+    recursive: true
+  - name: --local
+commands:
+  - name: remote
+    aliases: [r]
+    options:
+      - name: --config
+        recursive: true
+      - name: --colour
+        aliases: [--color]
+    commands:
+      - name: add
+        hidden: true
+        options:
+          - name: --very
+            aliases: [-v]
+"#,
+    )
+    .unwrap()
+}
+
+/// The names, sources and inheritance of the effective options at a path.
+fn effective(path: &[&str]) -> Vec<(String, String, bool)> {
+    document()
+        .effective_options(path)
+        .unwrap()
+        .into_iter()
+        .map(|effective| {
+            (
+                effective.option.name.clone(),
+                effective.source.join(" "),
+                effective.inherited,
+            )
+        })
+        .collect()
+}
+
+fn entry(name: &str, source: &str, inherited: bool) -> (String, String, bool) {
+    (name.to_owned(), source.to_owned(), inherited)
+}
+
+#[test]
+fn root_options() {
+    assert_eq!(
+        effective(&[]),
+        [
+            entry("--verbose", "", false),
+            entry("--color", "", false),
+            entry("--local", "", false),
+        ]
+    );
+}
+
+#[test]
+fn inherited_options_come_first() {
+    // `--colour` shadows `--color` through its alias and is not recursive, ending the inheritance
+    assert_eq!(
+        effective(&["remote"]),
+        [
+            entry("--verbose", "", true),
+            entry("--config", "remote", false),
+            entry("--colour", "remote", false),
+        ]
+    );
+}
+
+#[test]
+fn aliases_resolve_and_shadow() {
+    // Hidden commands are resolved, `-v` shadows `--verbose`
+    assert_eq!(
+        effective(&["r", "add"]),
+        [
+            entry("--config", "remote", true),
+            entry("--very", "remote add", false),
+        ]
+    );
+    assert_eq!(effective(&["r", "add"]), effective(&["remote", "add"]));
+}
+
+#[test]
+fn unknown_path() {
+    assert!(
+        document()
+            .effective_options(&["remote", "missing"])
+            .is_none()
+    );
+}
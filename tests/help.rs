@@ -0,0 +1,102 @@
+use opencli::OpenCliDocument;
+
+fn document() -> OpenCliDocument {
+    OpenCliDocument::from_yaml_str(
+        r#"
+opencli: "0.1"
+info:
+  title: mytool
+  version: "1.0"
+  description: A tool which does a great many things, described at length to show wrapping.
+options:
+  - name: --color
+    recursive: true
+    description: When to use colors in the output of every command
+    arguments: [{ name: when, acceptedValues: [auto, never] }]
+  - name: --secret
+    hidden: true
+commands:
+  - name: build
+    aliases: [b]
+    description: Build the project
+    options:
+      - name: --a-very-long-option-name-indeed
+        description: Moves the description to its own line
+    arguments:
+      - name: target
+        required: true
+        group: Targets
+  - name: internal
+    hidden: true
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn wraps_to_width() {
+    assert_eq!(
+        document().help_with_width(&[], 40).unwrap(),
+        "\
+A tool which does a great many things,
+described at length to show wrapping.
+
+Usage: mytool [OPTIONS] <COMMAND>
+
+Commands:
+  build, b        Build the project
+
+Options:
+  --color <when>  When to use colors in
+                  the output of every
+                  command [possible
+                  values: auto, never]
+"
+    );
+}
+
+#[test]
+fn long_names_and_groups() {
+    assert_eq!(
+        document().help_with_width(&["b"], 60).unwrap(),
+        "\
+Build the project
+
+Usage: mytool build [OPTIONS] <target>
+
+Aliases: b
+
+Options:
+  --color <when>  When to use colors in the output of every
+                  command [possible values: auto, never]
+  --a-very-long-option-name-indeed
+                  Moves the description to its own line
+
+Targets:
+  <target>        [required]
+"
+    );
+}
+
+#[test]
+fn unknown_command() {
+    assert_eq!(document().help_with_width(&["internal", "x"], 80), None);
+}
+
+#[test]
+fn width_from_columns() {
+    let document = document();
+    let narrow = document.help_with_width(&[], 40);
+    let default = document.help_with_width(&[], 80);
+    assert_ne!(narrow, default);
+
+    // SAFETY: no other test in this binary reads or writes the environment
+    unsafe { std::env::set_var("COLUMNS", "40") };
+    assert_eq!(document.help(&[]), narrow);
+    for columns in ["0", "wide", ""] {
+        unsafe { std::env::set_var("COLUMNS", columns) };
+        assert_eq!(document.help(&[]), default);
+    }
+    unsafe { std::env::remove_var("COLUMNS") };
+    assert_eq!(document.help(&[]), default);
+}
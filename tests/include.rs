@@ -0,0 +1,183 @@
+use std::path::{Path, PathBuf};
+
+use opencli::{Error, Format, OpenCliDocument};
+use serde_json::json;
+
+const MAIN: &str = "tests/data/include/main.yaml";
+
+#[test]
+fn resolve_includes() {
+    let document = OpenCliDocument::from_path(MAIN).unwrap();
+    let expected = OpenCliDocument::from_yaml_str(
+        r#"
+opencli: "0.1"
+info:
+  title: mytool
+  version: "1.0.0"
+options:
+  - name: --verbose
+    aliases: [-v]
+    recursive: true
+commands:
+  - name: build
+    description: Build the project
+    options:
+      - name: --jobs
+        arguments:
+          - name: count
+    arguments:
+      - name: count
+    exitCodes:
+      - code: 2
+        description: Invalid usage
+metadata:
+  - name: schema
+    value:
+      $ref: https://example.com/schema.json
+x-template:
+  $ref: template.yaml
+"#,
+    )
+    .unwrap();
+    assert_eq!(document, expected);
+}
+
+#[test]
+fn references_outside_entries_are_kept() {
+    let document = OpenCliDocument::from_path(MAIN).unwrap();
+    assert_eq!(
+        document.metadata[0].value,
+        Some(json!({ "$ref": "https://example.com/schema.json" }))
+    );
+    assert_eq!(
+        document.extensions["x-template"],
+        json!({ "$ref": "template.yaml" })
+    );
+
+    // Without any includes, the document is parsed as is
+    let document = OpenCliDocument::from_path("tests/data/include/metadata.yaml").unwrap();
+    assert_eq!(
+        document.metadata[0].value,
+        Some(json!({ "$ref": "schema.json" }))
+    );
+}
+
+#[test]
+fn bundle() {
+    let bundled = OpenCliDocument::bundle(MAIN, Format::Json).unwrap();
+    assert!(!bundled.contains("build.yaml"));
+    assert_eq!(
+        OpenCliDocument::from_json_str(&bundled).unwrap(),
+        OpenCliDocument::from_path(MAIN).unwrap()
+    );
+
+    let bundled = OpenCliDocument::bundle(MAIN, Format::Yaml).unwrap();
+    assert_eq!(
+        OpenCliDocument::from_yaml_str(&bundled).unwrap(),
+        OpenCliDocument::from_path(MAIN).unwrap()
+    );
+}
+
+#[test]
+fn include_cycle() {
+    let error = OpenCliDocument::from_path("tests/data/include/cycle/main.yaml").unwrap_err();
+    let Error::IncludeCycle(cycle) = &error else {
+        panic!("Expected an include cycle, got {error:?}");
+    };
+    let names: Vec<_> = cycle
+        .iter()
+        .map(|file| file.file_name().unwrap().to_str().unwrap())
+        .collect();
+    assert_eq!(names, ["main.yaml", "remote.yaml", "main.yaml"]);
+    assert!(error.to_string().starts_with("Include cycle: /"));
+}
+
+/// The included file an error occurred in and the error itself.
+fn include_error(path: &str) -> (PathBuf, Error) {
+    match OpenCliDocument::from_path(path).unwrap_err() {
+        Error::Include { path, source } => (path, *source),
+        error => panic!("Expected an include error, got {error:?}"),
+    }
+}
+
+#[test]
+fn missing_file() {
+    let (path, error) = include_error("tests/data/include/missing.yaml");
+    assert_eq!(path, Path::new("tests/data/include/commands/deploy.yaml"));
+    assert!(matches!(error, Error::Io(_)));
+}
+
+#[test]
+fn missing_pointer_target() {
+    let (path, error) = include_error("tests/data/include/pointer.yaml");
+    assert_eq!(path, Path::new("tests/data/include/shared.yaml"));
+    assert!(matches!(&error, Error::InvalidReference(location) if location == "options[0]"));
+}
+
+/// The field, line and column of a parse error.
+fn location(error: Error) -> (Option<String>, Option<usize>, Option<usize>) {
+    let Error::Parse { error, .. } = error else {
+        panic!("Expected a parse error, got {error:?}");
+    };
+    (error.path, error.line, error.column)
+}
+
+#[test]
+fn error_in_included_file() {
+    let (path, error) = include_error("tests/data/include/invalid.yaml");
+    assert_eq!(path, Path::new("tests/data/include/commands/invalid.yaml"));
+    assert_eq!(
+        error.to_string(),
+        "Parsing error: invalid YAML at `commands[0].hidden` (line 2, column 9): invalid type: string \"maybe\", expected a boolean"
+    );
+    assert_eq!(
+        location(error),
+        (Some("commands[0].hidden".to_owned()), Some(2), Some(9))
+    );
+
+    let (path, error) = include_error("tests/data/include/invalid-json.yaml");
+    assert_eq!(path, Path::new("tests/data/include/commands/invalid.json"));
+    assert_eq!(
+        location(error),
+        (
+            Some("commands[0].options[0].arguments[0].arity.minimum".to_owned()),
+            Some(4),
+            Some(84)
+        )
+    );
+}
+
+#[test]
+fn error_in_included_part_of_file() {
+    let (path, error) = include_error("tests/data/include/invalid-pointer.yaml");
+    assert_eq!(path, Path::new("tests/data/include/invalid-options.yaml"));
+    assert_eq!(
+        location(error),
+        (
+            Some("commands[0].options[0].recursive".to_owned()),
+            Some(4),
+            Some(16)
+        )
+    );
+}
+
+#[test]
+fn error_in_including_file() {
+    let error = OpenCliDocument::from_path("tests/data/include/invalid-root.yaml").unwrap_err();
+    assert_eq!(
+        location(error),
+        (Some("interactive".to_owned()), Some(5), Some(14))
+    );
+}
+
+#[test]
+fn invalid_reference() {
+    let error = OpenCliDocument::from_path("tests/data/include/reference.yaml").unwrap_err();
+    assert!(
+        matches!(&error, Error::InvalidReference(location) if location == "commands[0].arguments[0]")
+    );
+    assert_eq!(
+        error.to_string(),
+        "Invalid `$ref` at `commands[0].arguments[0]`"
+    );
+}
@@ -0,0 +1,85 @@
+use opencli::OpenCliDocument;
+
+fn document() -> OpenCliDocument {
+    OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap()
+}
+
+#[test]
+fn find_command_by_name_and_alias() {
+    let mut document = document();
+    assert_eq!(document.find_command(&["build"]).unwrap().name, "build");
+    assert_eq!(document.find_command(&["b"]).unwrap().name, "build");
+    assert_eq!(
+        document.find_command(&["remote", "rm"]).unwrap().name,
+        "remove"
+    );
+    // Hidden commands can be found
+    assert_eq!(
+        document.find_command(&["internal"]).unwrap().hidden,
+        Some(true)
+    );
+
+    assert!(document.find_command(&[]).is_none());
+    assert!(document.find_command(&["add"]).is_none());
+    assert!(document.find_command(&["remote", "add", "x"]).is_none());
+
+    document
+        .find_command_mut(&["remote", "rm"])
+        .unwrap()
+        .description = Some("Remove a remote".to_owned());
+    assert_eq!(
+        document.commands[1].commands[1].description.as_deref(),
+        Some("Remove a remote")
+    );
+}
+
+#[test]
+fn find_option_and_argument() {
+    let document = document();
+    assert_eq!(document.find_option(&[], "-v").unwrap().name, "--verbose");
+    assert_eq!(document.find_option(&["b"], "-o").unwrap().name, "--output");
+    assert!(document.find_option(&[], "--secret").is_some());
+    // Recursive options are only found where they are defined
+    assert!(document.find_option(&["build"], "--verbose").is_none());
+    assert!(document.find_option(&["missing"], "--verbose").is_none());
+
+    assert_eq!(
+        document
+            .find_argument(&["remote", "add"], "url")
+            .unwrap()
+            .required,
+        Some(true)
+    );
+    assert!(document.find_argument(&["remote", "add"], "URL").is_none());
+    assert!(document.find_argument(&[], "name").is_none());
+}
+
+#[test]
+fn iter_commands_depth_first() {
+    let document = document();
+    let commands: Vec<(Vec<&str>, Vec<&str>, usize)> = document
+        .iter_commands()
+        .map(|command| {
+            let parents = command
+                .parents
+                .iter()
+                .map(|parent| parent.name.as_str())
+                .collect();
+            (command.path.clone(), parents, command.depth())
+        })
+        .collect();
+    assert_eq!(
+        commands,
+        [
+            (vec!["build"], vec![], 1),
+            (vec!["remote"], vec![], 1),
+            (vec!["remote", "add"], vec!["remote"], 2),
+            (vec!["remote", "remove"], vec!["remote"], 2),
+            (vec!["internal"], vec![], 1),
+        ]
+    );
+
+    let remote = document.find_command(&["remote"]).unwrap();
+    let paths: Vec<Vec<&str>> = remote.iter_commands().map(|command| command.path).collect();
+    assert_eq!(paths, [vec!["add"], vec!["remove"]]);
+}
@@ -0,0 +1,76 @@
+use opencli::{OpenCliDocument, man};
+
+fn page(yaml: &str, name: &str) -> String {
+    let document = OpenCliDocument::from_yaml_str(yaml).unwrap();
+    man::render(&document)
+        .into_iter()
+        .find(|page| page.name == name)
+        .unwrap_or_else(|| panic!("no page named `{name}`"))
+        .content
+}
+
+#[test]
+fn pages_and_file_names() {
+    let document = OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap();
+    let names: Vec<String> = man::render(&document)
+        .iter()
+        .map(|page| page.file_name())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "mytool.1",
+            "mytool-build.1",
+            "mytool-remote.1",
+            "mytool-remote-add.1",
+            "mytool-remote-remove.1",
+        ]
+    );
+}
+
+#[test]
+fn synopsis_brackets_optional_positionals() {
+    let content = page(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+commands:
+  - name: copy
+    description: Copy files
+    options:
+      - name: --mode
+        aliases: [-m]
+        arguments: [{ name: mode }]
+      - name: --backup
+        required: true
+        arguments: [{ name: suffix, arity: { minimum: 0, maximum: 1 } }]
+    arguments:
+      - name: source
+        required: true
+        arity: { minimum: 1 }
+      - name: destination
+      - name: extra
+        arity: { minimum: 0, maximum: 2 }
+"#,
+        "mytool-copy",
+    );
+    let synopsis = content
+        .split(".SH SYNOPSIS\n")
+        .nth(1)
+        .and_then(|rest| rest.split(".SH ").next())
+        .unwrap();
+    assert_eq!(
+        synopsis,
+        "\
+.B mytool copy
+[\\fB\\-\\-mode\\fR|\\fB\\-m\\fR \\fImode\\fR]
+\\fB\\-\\-backup\\fR [\\fIsuffix\\fR]
+\\fIsource\\fR...
+[\\fIdestination\\fR]
+[\\fIextra\\fR...]
+"
+    );
+    assert!(content.starts_with(".TH MYTOOL\\-COPY 1 \"\" \"mytool 1.0\"\n"));
+    assert!(content.contains(".SH NAME\nmytool\\-copy \\- Copy files\n"));
+    assert!(content.contains(".SH \"SEE ALSO\"\n\\fBmytool\\fR(1)\n"));
+}
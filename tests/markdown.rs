@@ -0,0 +1,73 @@
+use opencli::{
+    OpenCliDocument,
+    markdown::{self, MarkdownFile, MarkdownOptions},
+};
+
+fn render(include_hidden: bool, split: bool) -> Vec<MarkdownFile> {
+    let document = OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap();
+    markdown::render(
+        &document,
+        &MarkdownOptions {
+            include_hidden,
+            split,
+        },
+    )
+}
+
+fn names(files: &[MarkdownFile]) -> Vec<&str> {
+    files.iter().map(|file| file.name.as_str()).collect()
+}
+
+#[test]
+fn single_file() {
+    let files = render(false, false);
+    assert_eq!(names(&files), ["mytool.md"]);
+
+    let content = &files[0].content;
+    assert!(content.starts_with("# mytool\n\nA sample tool\n\nVersion: `1.0`\n"));
+    assert!(content.contains("\n## mytool remote add\n"));
+    // Commands link to the sections of the same file
+    assert!(content.contains("| [`build`](#mytool-build) | `b` | Build the project |"));
+    assert!(content.contains("| [`add`](#mytool-remote-add) |  |  |"));
+}
+
+#[test]
+fn split_files() {
+    let files = render(false, true);
+    assert_eq!(
+        names(&files),
+        [
+            "mytool.md",
+            "mytool-build.md",
+            "mytool-remote.md",
+            "mytool-remote-add.md",
+            "mytool-remote-remove.md",
+        ]
+    );
+
+    // Every file is a standalone page linking to the pages of its sub commands
+    assert!(files[2].content.starts_with("# mytool remote\n"));
+    assert!(
+        files[2]
+            .content
+            .contains("| [`add`](mytool-remote-add.md) |")
+    );
+    assert!(files[0].content.contains("| [`build`](mytool-build.md) |"));
+    assert!(!files[0].content.contains("## mytool build"));
+}
+
+#[test]
+fn hidden_items() {
+    let visible = &render(false, false)[0].content;
+    assert!(!visible.contains("internal"));
+    assert!(!visible.contains("--secret"));
+
+    let all = render(true, true);
+    assert!(names(&all).contains(&"mytool-internal.md"));
+    assert!(all[0].content.contains("--secret"));
+    assert!(
+        all[0]
+            .content
+            .contains("| [`internal`](mytool-internal.md) |")
+    );
+}
@@ -0,0 +1,224 @@
+use opencli::{MergeConflict, MergeConflictKind, OpenCliDocument};
+use serde_json::json;
+
+fn document(body: &str) -> OpenCliDocument {
+    OpenCliDocument::from_yaml_str(&format!(
+        "opencli: \"0.1\"\ninfo: {{ title: mytool, version: \"1.0.0\" }}\n{body}"
+    ))
+    .unwrap()
+}
+
+fn merge(base: &str, overlay: &str) -> Result<OpenCliDocument, Vec<MergeConflict>> {
+    OpenCliDocument::merge(&document(base), &document(overlay))
+}
+
+fn conflict(path: &str, base: serde_json::Value, overlay: serde_json::Value) -> MergeConflict {
+    MergeConflict {
+        path: path.to_owned(),
+        kind: MergeConflictKind::Value { base, overlay },
+    }
+}
+
+#[test]
+fn merge_with_itself() {
+    let document = OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap();
+    assert_eq!(OpenCliDocument::merge(&document, &document), Ok(document));
+}
+
+#[test]
+fn overlay_adds_and_documents() {
+    let merged = merge(
+        r#"
+options: [{ name: --verbose, aliases: [-v], description: Old }]
+commands:
+  - name: build
+    exitCodes: [{ code: 1 }]
+    x-team: core
+"#,
+        r#"
+examples: [mytool build]
+options: [{ name: -v, aliases: [--debug], description: New, group: Output }]
+commands:
+  - name: build
+    aliases: [b]
+    exitCodes: [{ code: 1, description: Failure }, { code: 2 }]
+    options: [{ name: --release }]
+    x-owner: me
+  - name: test
+"#,
+    )
+    .unwrap();
+    let expected = document(
+        r#"
+examples: [mytool build]
+options: [{ name: --verbose, aliases: [-v, --debug], description: New, group: Output }]
+commands:
+  - name: build
+    aliases: [b]
+    options: [{ name: --release }]
+    exitCodes: [{ code: 1, description: Failure }, { code: 2 }]
+    x-team: core
+    x-owner: me
+  - name: test
+"#,
+    );
+    assert_eq!(merged, expected);
+}
+
+#[test]
+fn flag_and_valued_option_conflict() {
+    let flag = "options: [{ name: --color }]";
+    let valued = "options: [{ name: --color, arguments: [{ name: when }] }]";
+    assert_eq!(
+        merge(flag, valued),
+        Err(vec![conflict(
+            "options[0].arguments",
+            json!([]),
+            json!(["when"])
+        )])
+    );
+    assert_eq!(
+        merge(valued, flag),
+        Err(vec![conflict(
+            "options[0].arguments",
+            json!(["when"]),
+            json!([])
+        )])
+    );
+    assert_eq!(
+        merge(
+            valued,
+            "options: [{ name: --color, arguments: [{ name: mode }] }]"
+        ),
+        Err(vec![conflict(
+            "options[0].arguments",
+            json!(["when"]),
+            json!(["mode"])
+        )])
+    );
+}
+
+#[test]
+fn conventions_use_defaults() {
+    let merged = merge(
+        "",
+        "conventions: { groupOptions: true, optionArgumentSeparator: \" \" }",
+    )
+    .unwrap();
+    let conventions = merged.conventions.unwrap();
+    assert_eq!(conventions.group_options, Some(true));
+    assert_eq!(conventions.option_argument_separator.as_deref(), Some(" "));
+
+    assert_eq!(
+        merge(
+            "",
+            "conventions: { groupOptions: false, optionArgumentSeparator: \"=\" }"
+        ),
+        Err(vec![
+            conflict("conventions.groupOptions", json!(null), json!(false)),
+            conflict(
+                "conventions.optionArgumentSeparator",
+                json!(null),
+                json!("=")
+            ),
+        ])
+    );
+    assert_eq!(
+        merge(
+            "conventions: { groupOptions: false }",
+            "conventions: { groupOptions: true }"
+        ),
+        Err(vec![conflict(
+            "conventions.groupOptions",
+            json!(false),
+            json!(true)
+        )])
+    );
+}
+
+#[test]
+fn parsing_values_must_agree() {
+    // Unset values are the defaults
+    assert!(
+        merge(
+            "arguments: [{ name: file, arity: { minimum: 1, maximum: 1 } }]",
+            "arguments: [{ name: file, required: false }]",
+        )
+        .is_ok()
+    );
+    assert_eq!(
+        merge(
+            "commands: [{ name: build, options: [{ name: --jobs, recursive: true }], arguments: [{ name: target }] }]",
+            "commands: [{ name: build, options: [{ name: --jobs, recursive: false }], arguments: [{ name: target, required: true, arity: { minimum: 2 } }] }]",
+        ),
+        Err(vec![
+            conflict(
+                "commands[0].arguments[0].required",
+                json!(null),
+                json!(true)
+            ),
+            conflict(
+                "commands[0].arguments[0].arity",
+                json!(null),
+                json!({ "minimum": 2 })
+            ),
+            conflict(
+                "commands[0].options[0].recursive",
+                json!(true),
+                json!(false)
+            ),
+        ])
+    );
+}
+
+#[test]
+fn accepted_values_are_sets() {
+    let merged = merge(
+        "arguments: [{ name: when, acceptedValues: [auto, always, never] }]",
+        "arguments: [{ name: when, acceptedValues: [never, auto, always] }]",
+    )
+    .unwrap();
+    assert_eq!(
+        merged.arguments[0].accepted_values,
+        ["auto", "always", "never"]
+    );
+    // No accepted values accept anything
+    assert!(
+        merge(
+            "arguments: [{ name: when }]",
+            "arguments: [{ name: when, acceptedValues: [auto] }]"
+        )
+        .is_err()
+    );
+    assert_eq!(
+        merge(
+            "arguments: [{ name: when, acceptedValues: [auto, always] }]",
+            "arguments: [{ name: when, acceptedValues: [auto] }]"
+        ),
+        Err(vec![conflict(
+            "arguments[0].acceptedValues",
+            json!(["auto", "always"]),
+            json!(["auto"])
+        )])
+    );
+}
+
+#[test]
+fn ambiguous_names() {
+    let conflicts = merge(
+        "commands: [{ name: remove }, { name: rm }]",
+        "commands: [{ name: remove, aliases: [rm] }]",
+    )
+    .unwrap_err();
+    assert_eq!(
+        conflicts,
+        [MergeConflict {
+            path: "commands[0]".to_owned(),
+            kind: MergeConflictKind::AmbiguousName("remove".to_owned()),
+        }]
+    );
+    assert_eq!(
+        conflicts[0].to_string(),
+        "commands[0]: Name `remove` matches more than one item"
+    );
+}
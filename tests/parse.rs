@@ -0,0 +1,97 @@
+use opencli::{Error, Format, OpenCliDocument, ParseError};
+
+fn parse_error(result: Result<OpenCliDocument, Error>) -> (ParseError, Option<ParseError>) {
+    match result {
+        Err(Error::Parse { error, fallback }) => (*error, fallback.map(|fallback| *fallback)),
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+}
+
+#[test]
+fn yaml_error_location() {
+    let yaml = r#"opencli: "0.1"
+info:
+  title: mytool
+  version: "1.0"
+commands:
+  - name: build
+    options:
+      - name: --jobs
+        arguments:
+          - name: count
+            arity: { minimum: many }
+"#;
+    let (error, fallback) = parse_error(OpenCliDocument::from_yaml_str(yaml));
+    assert_eq!(error.format, Format::Yaml);
+    assert_eq!(
+        error.path.as_deref(),
+        Some("commands[0].options[0].arguments[0].arity.minimum")
+    );
+    assert_eq!((error.line, error.column), (Some(11), Some(31)));
+    assert_eq!(error.message, "invalid type: string \"many\", expected i32");
+    assert!(fallback.is_none());
+}
+
+#[test]
+fn json_error_location() {
+    let json = r#"{
+  "opencli": "0.1",
+  "info": { "title": "mytool", "version": "1.0" },
+  "exitCodes": [{ "code": "zero" }]
+}"#;
+    let (error, fallback) = parse_error(OpenCliDocument::from_json_str(json));
+    assert_eq!(error.format, Format::Json);
+    assert_eq!(error.path.as_deref(), Some("exitCodes[0].code"));
+    assert_eq!((error.line, error.column), (Some(4), Some(32)));
+    assert_eq!(error.message, "invalid type: string \"zero\", expected i32");
+    assert!(fallback.is_none());
+}
+
+#[test]
+fn missing_field_has_no_field_path() {
+    let (error, _) = parse_error(OpenCliDocument::from_yaml_str("opencli: \"0.1\"\n"));
+    assert_eq!(error.path, None);
+    assert_eq!(error.message, "missing field `info`");
+}
+
+#[test]
+fn detected_format_error_wins_over_fallback() {
+    // Detected as JSON, which fails, and YAML fails as well
+    let content = r#"{ "opencli": "0.1", "info": { "title": 1 }"#;
+    let (error, fallback) = parse_error(content.parse());
+    assert_eq!(error.format, Format::Json);
+    assert_eq!(fallback.map(|fallback| fallback.format), Some(Format::Yaml));
+
+    // Detected as YAML, which fails, and JSON fails as well
+    let content = "opencli: [\n";
+    let (error, fallback) = parse_error(content.parse());
+    assert_eq!(error.format, Format::Yaml);
+    assert_eq!(fallback.map(|fallback| fallback.format), Some(Format::Json));
+}
+
+#[test]
+fn display() {
+    let error = ParseError {
+        format: Format::Yaml,
+        path: Some("info.version".to_owned()),
+        line: Some(3),
+        column: Some(12),
+        message: "invalid type: sequence, expected a string".to_owned(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "invalid YAML at `info.version` (line 3, column 12): invalid type: sequence, expected a string"
+    );
+
+    let error = ParseError {
+        format: Format::Json,
+        path: None,
+        line: Some(1),
+        column: None,
+        message: "EOF while parsing an object".to_owned(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "invalid JSON (line 1): EOF while parsing an object"
+    );
+}
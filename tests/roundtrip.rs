@@ -0,0 +1,287 @@
+use std::fmt::Debug;
+
+use opencli::{
+    Format, OpenCliArgument, OpenCliArity, OpenCliCommand, OpenCliContact, OpenCliConventions,
+    OpenCliDocument, OpenCliExitCode, OpenCliInfo, OpenCliLicense, OpenCliMetadata, OpenCliOption,
+};
+use serde::{Serialize, de::DeserializeOwned};
+
+fn extensions() -> serde_json::Map<String, serde_json::Value> {
+    serde_json::json!({ "x-vendor": { "id": 7 } })
+        .as_object()
+        .unwrap()
+        .clone()
+}
+
+fn metadata() -> Vec<OpenCliMetadata> {
+    vec![
+        OpenCliMetadata {
+            name: "language".to_owned(),
+            value: Some(serde_json::json!("rust")),
+            extensions: extensions(),
+        },
+        OpenCliMetadata {
+            name: "tags".to_owned(),
+            value: Some(serde_json::json!({ "stable": true, "since": 3 })),
+            extensions: Default::default(),
+        },
+        OpenCliMetadata {
+            name: "empty".to_owned(),
+            value: None,
+            extensions: Default::default(),
+        },
+    ]
+}
+
+fn arity() -> OpenCliArity {
+    OpenCliArity {
+        minimum: Some(1),
+        maximum: Some(3),
+        extensions: extensions(),
+    }
+}
+
+fn argument() -> OpenCliArgument {
+    OpenCliArgument {
+        name: "target".to_owned(),
+        required: Some(true),
+        arity: Some(arity()),
+        accepted_values: vec!["debug".to_owned(), "release".to_owned()],
+        group: Some("Build".to_owned()),
+        description: Some("The build target".to_owned()),
+        hidden: Some(false),
+        metadata: metadata(),
+        extensions: extensions(),
+    }
+}
+
+fn option() -> OpenCliOption {
+    OpenCliOption {
+        name: "--output".to_owned(),
+        required: Some(false),
+        aliases: vec!["-o".to_owned()],
+        arguments: vec![argument()],
+        group: Some("Output".to_owned()),
+        description: Some("The output path".to_owned()),
+        recursive: Some(true),
+        hidden: Some(false),
+        metadata: metadata(),
+        extensions: extensions(),
+    }
+}
+
+fn exit_code() -> OpenCliExitCode {
+    OpenCliExitCode {
+        code: 2,
+        description: Some("Invalid usage".to_owned()),
+        extensions: extensions(),
+    }
+}
+
+fn command() -> OpenCliCommand {
+    OpenCliCommand {
+        name: "build".to_owned(),
+        aliases: vec!["b".to_owned()],
+        options: vec![option()],
+        arguments: vec![argument()],
+        commands: vec![OpenCliCommand {
+            name: "docs".to_owned(),
+            hidden: Some(true),
+            ..Default::default()
+        }],
+        exit_codes: vec![exit_code()],
+        description: Some("Build the project".to_owned()),
+        hidden: Some(false),
+        examples: vec!["mytool build release".to_owned()],
+        interactive: Some(false),
+        metadata: metadata(),
+        extensions: extensions(),
+    }
+}
+
+fn contact() -> OpenCliContact {
+    OpenCliContact {
+        name: Some("Jane Doe".to_owned()),
+        url: Some("https://example.com".to_owned()),
+        email: Some("jane@example.com".to_owned()),
+        extensions: extensions(),
+    }
+}
+
+fn license() -> OpenCliLicense {
+    OpenCliLicense {
+        name: Some("MIT License".to_owned()),
+        identifier: Some("MIT".to_owned()),
+        extensions: extensions(),
+    }
+}
+
+fn info() -> OpenCliInfo {
+    OpenCliInfo {
+        title: "mytool".to_owned(),
+        summary: Some("A tool".to_owned()),
+        description: Some("A tool which does things".to_owned()),
+        contact: Some(contact()),
+        license: Some(license()),
+        version: "1.2.3".to_owned(),
+        extensions: extensions(),
+    }
+}
+
+fn conventions() -> OpenCliConventions {
+    OpenCliConventions {
+        group_options: Some(true),
+        option_argument_separator: Some("=".to_owned()),
+        extensions: extensions(),
+    }
+}
+
+fn document() -> OpenCliDocument {
+    OpenCliDocument {
+        opencli: "0.1".to_owned(),
+        info: info(),
+        conventions: Some(conventions()),
+        arguments: vec![argument()],
+        options: vec![option()],
+        commands: vec![command()],
+        exit_codes: vec![exit_code()],
+        examples: vec!["mytool --help".to_owned()],
+        interactive: Some(true),
+        metadata: metadata(),
+        extensions: extensions(),
+    }
+}
+
+fn assert_roundtrip<T>(value: T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let yaml = serde_yaml::to_string(&value).unwrap();
+    assert_eq!(serde_yaml::from_str::<T>(&yaml).unwrap(), value);
+
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value);
+}
+
+#[test]
+fn roundtrip_every_struct() {
+    assert_roundtrip(document());
+    assert_roundtrip(info());
+    assert_roundtrip(conventions());
+    assert_roundtrip(contact());
+    assert_roundtrip(license());
+    assert_roundtrip(command());
+    assert_roundtrip(argument());
+    assert_roundtrip(option());
+    assert_roundtrip(arity());
+    assert_roundtrip(exit_code());
+    assert_roundtrip(metadata());
+}
+
+#[test]
+fn roundtrip_default_document() {
+    let document = OpenCliDocument::default();
+
+    let yaml = document.to_yaml_string().unwrap();
+    assert_eq!(OpenCliDocument::from_yaml_str(&yaml).unwrap(), document);
+
+    let json = document.to_json_string(false).unwrap();
+    assert_eq!(OpenCliDocument::from_json_str(&json).unwrap(), document);
+}
+
+#[test]
+fn roundtrip_yaml_string() {
+    let yaml = document().to_yaml_string().unwrap();
+    assert_eq!(OpenCliDocument::from_yaml_str(&yaml).unwrap(), document());
+    assert_eq!(yaml.parse::<OpenCliDocument>().unwrap(), document());
+}
+
+#[test]
+fn roundtrip_json_string() {
+    for pretty in [false, true] {
+        let json = document().to_json_string(pretty).unwrap();
+        assert_eq!(OpenCliDocument::from_json_str(&json).unwrap(), document());
+        assert_eq!(json.parse::<OpenCliDocument>().unwrap(), document());
+    }
+}
+
+#[test]
+fn roundtrip_writer_and_reader() {
+    for format in [Format::Yaml, Format::Json] {
+        let mut buffer = Vec::new();
+        document().to_writer(&mut buffer, format).unwrap();
+        assert_eq!(Format::detect(str::from_utf8(&buffer).unwrap()), format);
+        assert_eq!(
+            OpenCliDocument::from_reader(buffer.as_slice()).unwrap(),
+            document()
+        );
+    }
+}
+
+#[test]
+fn roundtrip_path() {
+    let directory = std::env::temp_dir().join(format!("opencli-roundtrip-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    for (file, format) in [
+        ("opencli.yaml", Format::Yaml),
+        ("opencli.yml", Format::Yaml),
+        ("opencli.json", Format::Json),
+    ] {
+        let path = directory.join(file);
+        document().to_path(&path).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(Format::detect(&content), format);
+        assert_eq!(OpenCliDocument::from_path(&path).unwrap(), document());
+    }
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn explicit_format_does_not_fall_back() {
+    let json = document().to_json_string(false).unwrap();
+    let yaml = document().to_yaml_string().unwrap();
+
+    let Err(opencli::Error::Parse { error, fallback }) = OpenCliDocument::from_json_str(&yaml)
+    else {
+        panic!("expected a parse error");
+    };
+    assert_eq!(error.format, Format::Json);
+    assert!(fallback.is_none());
+
+    // JSON is a subset of YAML
+    assert_eq!(OpenCliDocument::from_yaml_str(&json).unwrap(), document());
+}
+
+#[test]
+fn unknown_fields_are_preserved() {
+    let yaml = r#"
+opencli: "0.1"
+x-generator: mytool-docs
+info:
+  title: mytool
+  version: "1.0"
+  termsOfService: https://example.com/terms
+commands:
+  - name: build
+    x-owner: build-team
+    options:
+      - name: --jobs
+        deprecated: true
+        arguments:
+          - name: count
+            x-format: integer
+"#;
+    let document = OpenCliDocument::from_yaml_str(yaml).unwrap();
+    assert_eq!(document.extensions["x-generator"], "mytool-docs");
+    assert_eq!(
+        document.commands[0].options[0].extensions["deprecated"],
+        true
+    );
+
+    let original: serde_json::Value = serde_yaml::from_str(yaml).unwrap();
+    let written: serde_json::Value =
+        serde_yaml::from_str(&document.to_yaml_string().unwrap()).unwrap();
+    assert_eq!(written, original);
+}
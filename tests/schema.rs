@@ -0,0 +1,157 @@
+#![cfg(feature = "schemars")]
+
+use opencli::OpenCliDocument;
+use serde_json::{Value, json};
+
+/// The names of the properties of an object schema, in order.
+fn properties(schema: &Value) -> Vec<&str> {
+    schema["properties"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect()
+}
+
+/// Check a value against the subset of JSON Schema used by the generated schema, treating
+/// properties not in the schema as errors to catch misspelled field names.
+fn check(root: &Value, schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let schema = root
+            .pointer(reference.trim_start_matches('#'))
+            .ok_or_else(|| format!("{path}: unknown reference {reference}"))?;
+        return check(root, schema, value, path);
+    }
+    if let Some(schemas) = schema.get("anyOf").and_then(Value::as_array) {
+        if !schemas
+            .iter()
+            .any(|schema| check(root, schema, value, path).is_ok())
+        {
+            return Err(format!("{path}: no schema matches"));
+        }
+        return Ok(());
+    }
+    if let Some(types) = schema.get("type") {
+        let types: Vec<&str> = match types {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            types => vec![types.as_str().unwrap()],
+        };
+        let matches = |name: &str| match (name, value) {
+            ("null", Value::Null)
+            | ("boolean", Value::Bool(_))
+            | ("string", Value::String(_))
+            | ("array", Value::Array(_))
+            | ("object", Value::Object(_)) => true,
+            ("integer", Value::Number(number)) => number.is_i64(),
+            ("number", Value::Number(_)) => true,
+            _ => false,
+        };
+        if !types.into_iter().any(matches) {
+            return Err(format!("{path}: unexpected type of {value}"));
+        }
+    }
+    if let Value::Object(object) = value {
+        for required in schema["required"].as_array().into_iter().flatten() {
+            let required = required.as_str().unwrap();
+            if !object.contains_key(required) {
+                return Err(format!("{path}: missing {required}"));
+            }
+        }
+        for (key, value) in object {
+            let schema = schema["properties"]
+                .get(key)
+                .ok_or_else(|| format!("{path}: unknown property {key}"))?;
+            check(root, schema, value, &format!("{path}/{key}"))?;
+        }
+    }
+    if let (Some(items), Value::Array(values)) = (schema.get("items"), value) {
+        for (i, value) in values.iter().enumerate() {
+            check(root, items, value, &format!("{path}/{i}"))?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn schema_structure() {
+    let schema = OpenCliDocument::json_schema();
+    assert_eq!(schema["title"], "OpenCLI");
+    assert_eq!(schema["required"], json!(["opencli", "info"]));
+    assert_eq!(
+        properties(&schema),
+        [
+            "arguments",
+            "commands",
+            "conventions",
+            "examples",
+            "exitCodes",
+            "info",
+            "interactive",
+            "metadata",
+            "opencli",
+            "options",
+        ]
+    );
+
+    let definitions = &schema["$defs"];
+    assert_eq!(
+        definitions["OpenCliInfo"]["required"],
+        json!(["title", "version"])
+    );
+    assert_eq!(definitions["OpenCliCommand"]["required"], json!(["name"]));
+    assert_eq!(definitions["OpenCliOption"]["required"], json!(["name"]));
+    assert_eq!(definitions["OpenCliArgument"]["required"], json!(["name"]));
+    assert_eq!(definitions["OpenCliExitCode"]["required"], json!(["code"]));
+    assert_eq!(definitions["OpenCliMetadata"]["required"], json!(["name"]));
+    assert_eq!(definitions["OpenCliArity"].get("required"), None);
+    assert_eq!(
+        properties(&definitions["OpenCliOption"]),
+        [
+            "aliases",
+            "arguments",
+            "description",
+            "group",
+            "hidden",
+            "metadata",
+            "name",
+            "recursive",
+            "required",
+        ]
+    );
+    assert_eq!(
+        properties(&definitions["OpenCliConventions"]),
+        ["groupOptions", "optionArgumentSeparator"]
+    );
+    // Unknown fields are kept, so they are allowed
+    assert_eq!(definitions["OpenCliCommand"]["additionalProperties"], true);
+}
+
+#[test]
+fn schema_accepts_fixture() {
+    let schema = OpenCliDocument::json_schema();
+    let document = OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap();
+    let value = serde_json::to_value(&document).unwrap();
+    check(&schema, &schema, &value, "").unwrap();
+
+    let content = std::fs::read_to_string("tests/data/mytool.yaml").unwrap();
+    let value: Value = serde_yaml::from_str(&content).unwrap();
+    check(&schema, &schema, &value, "").unwrap();
+}
+
+#[test]
+fn schema_rejects_invalid_documents() {
+    let schema = OpenCliDocument::json_schema();
+    let check = |value: Value| check(&schema, &schema, &value, "");
+    assert_eq!(
+        check(json!({ "opencli": "0.1" })),
+        Err(": missing info".to_owned())
+    );
+    assert_eq!(
+        check(json!({
+            "opencli": "0.1",
+            "info": { "title": "mytool", "version": "1.0.0" },
+            "commands": [{ "name": "build", "hidden": "yes" }],
+        })),
+        Err("/commands/0/hidden: unexpected type of \"yes\"".to_owned())
+    );
+}
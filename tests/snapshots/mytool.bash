@@ -0,0 +1,106 @@
+_mytool() {
+    local cur prev cmd i
+    COMPREPLY=()
+    cur="${COMP_WORDS[COMP_CWORD]}"
+    prev="${COMP_WORDS[COMP_CWORD-1]}"
+    cmd='mytool'
+
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case "${cmd},${COMP_WORDS[i]}" in
+            'mytool,build'|'mytool,b')
+                cmd='mytool__build'
+                ;;
+            'mytool,remote')
+                cmd='mytool__remote'
+                ;;
+            'mytool__remote,add')
+                cmd='mytool__remote__add'
+                ;;
+            'mytool__remote,remove'|'mytool__remote,rm')
+                cmd='mytool__remote__remove'
+                ;;
+        esac
+    done
+
+    case "${cmd}" in
+        'mytool')
+            case "${prev}" in
+                '--color')
+                    COMPREPLY=($(compgen -W 'auto always never' -- "${cur}"))
+                    return 0
+                    ;;
+            esac
+            if [[ "${cur}" == -* ]]; then
+                COMPREPLY=($(compgen -W '--verbose -v --color' -- "${cur}"))
+            else
+                COMPREPLY=($(compgen -W 'build b remote' -- "${cur}"))
+            fi
+            return 0
+            ;;
+        'mytool__build')
+            case "${prev}" in
+                '--color')
+                    COMPREPLY=($(compgen -W 'auto always never' -- "${cur}"))
+                    return 0
+                    ;;
+                '--output'|'-o')
+                    return 0
+                    ;;
+                '--profile')
+                    COMPREPLY=($(compgen -W 'debug release' -- "${cur}"))
+                    return 0
+                    ;;
+            esac
+            if [[ "${cur}" == -* ]]; then
+                COMPREPLY=($(compgen -W '--verbose -v --color --output -o --profile' -- "${cur}"))
+            else
+                COMPREPLY=($(compgen -W 'lib bin' -- "${cur}"))
+            fi
+            return 0
+            ;;
+        'mytool__remote')
+            case "${prev}" in
+                '--color')
+                    COMPREPLY=($(compgen -W 'auto always never' -- "${cur}"))
+                    return 0
+                    ;;
+            esac
+            if [[ "${cur}" == -* ]]; then
+                COMPREPLY=($(compgen -W '--verbose -v --color' -- "${cur}"))
+            else
+                COMPREPLY=($(compgen -W 'add remove rm' -- "${cur}"))
+            fi
+            return 0
+            ;;
+        'mytool__remote__add')
+            case "${prev}" in
+                '--color')
+                    COMPREPLY=($(compgen -W 'auto always never' -- "${cur}"))
+                    return 0
+                    ;;
+            esac
+            if [[ "${cur}" == -* ]]; then
+                COMPREPLY=($(compgen -W '--verbose -v --color' -- "${cur}"))
+            else
+                COMPREPLY=($(compgen -W '' -- "${cur}"))
+            fi
+            return 0
+            ;;
+        'mytool__remote__remove')
+            case "${prev}" in
+                '--color')
+                    COMPREPLY=($(compgen -W 'auto always never' -- "${cur}"))
+                    return 0
+                    ;;
+            esac
+            if [[ "${cur}" == -* ]]; then
+                COMPREPLY=($(compgen -W '--verbose -v --color' -- "${cur}"))
+            else
+                COMPREPLY=($(compgen -W '' -- "${cur}"))
+            fi
+            return 0
+            ;;
+    esac
+}
+
+complete -F _mytool -o bashdefault -o default 'mytool'
@@ -0,0 +1,44 @@
+function __fish_mytool_command_path
+    set -l path 'mytool'
+    for token in (commandline -opc)[2..-1]
+        switch "$path,$token"
+            case 'mytool,build' 'mytool,b'
+                set path 'mytool__build'
+            case 'mytool,remote'
+                set path 'mytool__remote'
+            case 'mytool__remote,add'
+                set path 'mytool__remote__add'
+            case 'mytool__remote,remove' 'mytool__remote,rm'
+                set path 'mytool__remote__remove'
+        end
+    end
+    echo $path
+end
+
+function __fish_mytool_at
+    test (__fish_mytool_command_path) = $argv[1]
+end
+
+complete -c 'mytool' -n '__fish_mytool_at \'mytool\'' -f -a 'build' -d 'Build the project'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool\'' -f -a 'b' -d 'Build the project'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool\'' -f -a 'remote'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool\'' -l 'verbose' -s 'v' -d 'Verbose output'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool\'' -l 'color' -r -f -a 'auto always never'
+
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__build\'' -l 'verbose' -s 'v' -d 'Verbose output'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__build\'' -l 'color' -r -f -a 'auto always never'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__build\'' -l 'output' -s 'o' -r -d 'Output directory'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__build\'' -l 'profile' -r -f -a 'debug release'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__build\'' -f -a 'lib bin'
+
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__remote\'' -f -a 'add'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__remote\'' -f -a 'remove'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__remote\'' -f -a 'rm'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__remote\'' -l 'verbose' -s 'v' -d 'Verbose output'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__remote\'' -l 'color' -r -f -a 'auto always never'
+
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__remote__add\'' -l 'verbose' -s 'v' -d 'Verbose output'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__remote__add\'' -l 'color' -r -f -a 'auto always never'
+
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__remote__remove\'' -l 'verbose' -s 'v' -d 'Verbose output'
+complete -c 'mytool' -n '__fish_mytool_at \'mytool__remote__remove\'' -l 'color' -r -f -a 'auto always never'
@@ -0,0 +1,115 @@
+using namespace System.Management.Automation
+using namespace System.Management.Automation.Language
+
+Register-ArgumentCompleter -Native -CommandName 'mytool' -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $commandElements = $commandAst.CommandElements
+    $command = 'mytool'
+    for ($i = 1; $i -lt $commandElements.Count; $i++) {
+        $element = $commandElements[$i]
+        if ($element -isnot [StringConstantExpressionAst] -or
+            $element.StringConstantType -ne [StringConstantType]::BareWord -or
+            $element.Value.StartsWith('-') -or
+            $element.Value -eq $wordToComplete) {
+            continue
+        }
+        switch ("$command;$($element.Value)") {
+            'mytool;build' { $command = 'mytool__build'; break }
+            'mytool;b' { $command = 'mytool__build'; break }
+            'mytool;remote' { $command = 'mytool__remote'; break }
+            'mytool__remote;add' { $command = 'mytool__remote__add'; break }
+            'mytool__remote;remove' { $command = 'mytool__remote__remove'; break }
+            'mytool__remote;rm' { $command = 'mytool__remote__remove'; break }
+        }
+    }
+
+    $previous = ''
+    $last = if ($wordToComplete) { $commandElements.Count - 2 } else { $commandElements.Count - 1 }
+    if ($last -ge 1) { $previous = $commandElements[$last].Extent.Text }
+
+    $completions = @(switch ($command) {
+        'mytool' {
+            if ($previous -in '--color') {
+                [CompletionResult]::new('auto', 'auto', [CompletionResultType]::ParameterValue, 'auto')
+                [CompletionResult]::new('always', 'always', [CompletionResultType]::ParameterValue, 'always')
+                [CompletionResult]::new('never', 'never', [CompletionResultType]::ParameterValue, 'never')
+                break
+            }
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Verbose output')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Verbose output')
+            [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, '--color')
+            [CompletionResult]::new('build', 'build', [CompletionResultType]::ParameterValue, 'Build the project')
+            [CompletionResult]::new('b', 'b', [CompletionResultType]::ParameterValue, 'Build the project')
+            [CompletionResult]::new('remote', 'remote', [CompletionResultType]::ParameterValue, 'remote')
+            break
+        }
+        'mytool__build' {
+            if ($previous -in '--color') {
+                [CompletionResult]::new('auto', 'auto', [CompletionResultType]::ParameterValue, 'auto')
+                [CompletionResult]::new('always', 'always', [CompletionResultType]::ParameterValue, 'always')
+                [CompletionResult]::new('never', 'never', [CompletionResultType]::ParameterValue, 'never')
+                break
+            }
+            if ($previous -in '--output', '-o') {
+                break
+            }
+            if ($previous -in '--profile') {
+                [CompletionResult]::new('debug', 'debug', [CompletionResultType]::ParameterValue, 'debug')
+                [CompletionResult]::new('release', 'release', [CompletionResultType]::ParameterValue, 'release')
+                break
+            }
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Verbose output')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Verbose output')
+            [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, '--color')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output directory')
+            [CompletionResult]::new('-o', '-o', [CompletionResultType]::ParameterName, 'Output directory')
+            [CompletionResult]::new('--profile', '--profile', [CompletionResultType]::ParameterName, '--profile')
+            [CompletionResult]::new('lib', 'lib', [CompletionResultType]::ParameterValue, 'lib')
+            [CompletionResult]::new('bin', 'bin', [CompletionResultType]::ParameterValue, 'bin')
+            break
+        }
+        'mytool__remote' {
+            if ($previous -in '--color') {
+                [CompletionResult]::new('auto', 'auto', [CompletionResultType]::ParameterValue, 'auto')
+                [CompletionResult]::new('always', 'always', [CompletionResultType]::ParameterValue, 'always')
+                [CompletionResult]::new('never', 'never', [CompletionResultType]::ParameterValue, 'never')
+                break
+            }
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Verbose output')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Verbose output')
+            [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, '--color')
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'add')
+            [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'remove')
+            [CompletionResult]::new('rm', 'rm', [CompletionResultType]::ParameterValue, 'rm')
+            break
+        }
+        'mytool__remote__add' {
+            if ($previous -in '--color') {
+                [CompletionResult]::new('auto', 'auto', [CompletionResultType]::ParameterValue, 'auto')
+                [CompletionResult]::new('always', 'always', [CompletionResultType]::ParameterValue, 'always')
+                [CompletionResult]::new('never', 'never', [CompletionResultType]::ParameterValue, 'never')
+                break
+            }
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Verbose output')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Verbose output')
+            [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, '--color')
+            break
+        }
+        'mytool__remote__remove' {
+            if ($previous -in '--color') {
+                [CompletionResult]::new('auto', 'auto', [CompletionResultType]::ParameterValue, 'auto')
+                [CompletionResult]::new('always', 'always', [CompletionResultType]::ParameterValue, 'always')
+                [CompletionResult]::new('never', 'never', [CompletionResultType]::ParameterValue, 'never')
+                break
+            }
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Verbose output')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Verbose output')
+            [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, '--color')
+            break
+        }
+    })
+
+    $completions.Where{ $_.CompletionText -like "$wordToComplete*" } |
+        Sort-Object -Property ListItemText
+}
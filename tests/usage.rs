@@ -0,0 +1,102 @@
+use opencli::{OpenCliCommand, OpenCliConventions, OpenCliDocument};
+
+fn document() -> OpenCliDocument {
+    OpenCliDocument::from_yaml_str(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+options:
+  - name: --verbose
+    recursive: true
+commands:
+  - name: copy
+    aliases: [cp]
+    options:
+      - name: --mode
+        aliases: [-m]
+        required: true
+        arguments: [{ name: mode }]
+      - name: --backup
+        required: true
+        arguments: [{ name: suffix, arity: { minimum: 0, maximum: 1 } }]
+      - name: --secret
+        required: true
+        hidden: true
+    arguments:
+      - name: source
+        required: true
+        arity: { minimum: 1 }
+      - name: destination
+        required: true
+      - name: extra
+        arity: { minimum: 0, maximum: 3 }
+  - name: fixed
+    arguments:
+      - name: pair
+        required: true
+        arity: { minimum: 2, maximum: 2 }
+      - name: range
+        required: true
+        arity: { minimum: 1, maximum: 3 }
+      - name: many
+        arity: { minimum: 2 }
+      - name: none
+        arity: { minimum: 0, maximum: 0 }
+  - name: internal
+    hidden: true
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn root_usage() {
+    assert_eq!(document().usage(), "mytool [OPTIONS] <COMMAND>");
+}
+
+#[test]
+fn arity_rendering() {
+    assert_eq!(
+        document().command_usage(&["fixed"]).unwrap(),
+        "mytool fixed [OPTIONS] <pair>{2} <range>{1,3} [<many>{2,}]"
+    );
+}
+
+#[test]
+fn bracket_placement() {
+    // Required options are listed, values after an unbounded argument need `--`
+    assert_eq!(
+        document().command_usage(&["cp"]).unwrap(),
+        "mytool copy [OPTIONS] (--mode|-m) <mode> --backup [<suffix>] <source>... [--] <destination> [<extra>{1,3}]"
+    );
+}
+
+#[test]
+fn separator_convention() {
+    let mut document = document();
+    document.conventions = Some(OpenCliConventions {
+        option_argument_separator: Some("=".to_owned()),
+        ..Default::default()
+    });
+    assert_eq!(
+        document.command_usage(&["copy"]).unwrap(),
+        "mytool copy [OPTIONS] (--mode|-m)=<mode> --backup[=<suffix>] <source>... [--] <destination> [<extra>{1,3}]"
+    );
+}
+
+#[test]
+fn command_usage() {
+    let document = document();
+    assert_eq!(document.command_usage(&["missing"]), None);
+
+    // Without a document, inherited options are unknown
+    let command: &OpenCliCommand = &document.commands[1];
+    assert_eq!(
+        command.usage("mytool", None),
+        "mytool fixed <pair>{2} <range>{1,3} [<many>{2,}]"
+    );
+    assert_eq!(
+        command.usage("", None),
+        "fixed <pair>{2} <range>{1,3} [<many>{2,}]"
+    );
+}
@@ -0,0 +1,173 @@
+use opencli::{OpenCliDocument, ValidationError, ValidationErrorKind};
+
+fn errors(yaml: &str) -> Vec<(String, ValidationErrorKind)> {
+    let document = OpenCliDocument::from_yaml_str(yaml).unwrap();
+    match document.validate() {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .into_iter()
+            .map(|ValidationError { path, kind }| (path, kind))
+            .collect(),
+    }
+}
+
+#[test]
+fn valid_document() {
+    let document = OpenCliDocument::from_path("tests/data/mytool.yaml").unwrap();
+    assert_eq!(document.validate(), Ok(()));
+}
+
+#[test]
+fn empty_fields() {
+    let errors = errors(
+        r#"
+opencli: ""
+info: { title: " ", version: "" }
+commands:
+  - name: build
+    aliases: [""]
+    options: [{ name: "" }]
+"#,
+    );
+    assert_eq!(
+        errors,
+        [
+            ("opencli".to_owned(), ValidationErrorKind::Empty),
+            ("info.title".to_owned(), ValidationErrorKind::Empty),
+            ("info.version".to_owned(), ValidationErrorKind::Empty),
+            (
+                "commands[0].aliases[0]".to_owned(),
+                ValidationErrorKind::Empty
+            ),
+            (
+                "commands[0].options[0].name".to_owned(),
+                ValidationErrorKind::Empty
+            ),
+        ]
+    );
+}
+
+#[test]
+fn duplicate_names() {
+    let errors = errors(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+options:
+  - name: --verbose
+    aliases: [-v]
+  - name: --version
+    aliases: [-v]
+commands:
+  - name: remote
+    aliases: [r]
+    arguments: [{ name: url }, { name: url }]
+  - name: r
+"#,
+    );
+    assert_eq!(
+        errors,
+        [
+            (
+                "options[1].aliases[0]".to_owned(),
+                ValidationErrorKind::DuplicateName("-v".to_owned())
+            ),
+            (
+                "commands[0].arguments[1].name".to_owned(),
+                ValidationErrorKind::DuplicateName("url".to_owned())
+            ),
+            (
+                "commands[1].name".to_owned(),
+                ValidationErrorKind::DuplicateName("r".to_owned())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn duplicate_exit_codes() {
+    let errors = errors(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+exitCodes: [{ code: 0 }, { code: 1 }, { code: 0 }]
+commands:
+  - name: build
+    exitCodes: [{ code: 2 }, { code: 2 }]
+"#,
+    );
+    assert_eq!(
+        errors,
+        [
+            (
+                "commands[0].exitCodes[1].code".to_owned(),
+                ValidationErrorKind::DuplicateExitCode(2)
+            ),
+            (
+                "exitCodes[2].code".to_owned(),
+                ValidationErrorKind::DuplicateExitCode(0)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn invalid_arity() {
+    let errors = errors(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+commands:
+  - name: build
+    options:
+      - name: --jobs
+        arguments: [{ name: count, arity: { minimum: -1 } }]
+    commands:
+      - name: docs
+        arguments: [{ name: page, arity: { minimum: 3, maximum: 2 } }]
+"#,
+    );
+    assert_eq!(
+        errors,
+        [
+            (
+                "commands[0].options[0].arguments[0].arity".to_owned(),
+                ValidationErrorKind::NegativeArity(-1)
+            ),
+            (
+                "commands[0].commands[0].arguments[0].arity".to_owned(),
+                ValidationErrorKind::InvalidArity {
+                    minimum: 3,
+                    maximum: 2
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn option_and_argument_may_share_a_name() {
+    let errors = errors(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+commands:
+  - name: build
+    options: [{ name: --target }]
+    arguments: [{ name: target }]
+"#,
+    );
+    assert_eq!(errors, []);
+}
+
+#[test]
+fn error_display() {
+    let error = ValidationError {
+        path: "commands[1].exitCodes[0].code".to_owned(),
+        kind: ValidationErrorKind::DuplicateExitCode(2),
+    };
+    assert_eq!(
+        error.to_string(),
+        "commands[1].exitCodes[0].code: Duplicate exit code 2"
+    );
+}
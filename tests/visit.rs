@@ -0,0 +1,159 @@
+use opencli::visit::{self, Context, Visit, VisitMut};
+use opencli::{
+    OpenCliArgument, OpenCliCommand, OpenCliDocument, OpenCliExitCode, OpenCliMetadata,
+    OpenCliOption,
+};
+
+fn document() -> OpenCliDocument {
+    OpenCliDocument::from_yaml_str(
+        r#"
+opencli: "0.1"
+info: { title: mytool, version: "1.0" }
+options:
+  - name: --color
+    arguments: [{ name: when, metadata: [{ name: format }] }]
+arguments: [{ name: file }]
+commands:
+  - name: remote
+    options: [{ name: --config }]
+    commands:
+      - name: add
+        arguments: [{ name: url }]
+        exitCodes: [{ code: 3 }]
+  - name: build
+exitCodes: [{ code: 0 }]
+metadata: [{ name: language }]
+"#,
+    )
+    .unwrap()
+}
+
+/// Records every visited item with its context.
+#[derive(Default)]
+struct Recorder {
+    visited: Vec<String>,
+}
+
+impl Recorder {
+    fn record(&mut self, context: &Context, item: &str) {
+        self.visited.push(format!(
+            "{} [{}] {item}",
+            context.path,
+            context.commands.join(" ")
+        ));
+    }
+}
+
+impl<'a> Visit<'a> for Recorder {
+    fn visit_command(&mut self, context: &Context, command: &'a OpenCliCommand) {
+        self.record(context, &command.name);
+        visit::walk_command(self, context, command);
+    }
+
+    fn visit_option(&mut self, context: &Context, option: &'a OpenCliOption) {
+        self.record(context, &option.name);
+        visit::walk_option(self, context, option);
+    }
+
+    fn visit_argument(&mut self, context: &Context, argument: &'a OpenCliArgument) {
+        self.record(context, &argument.name);
+        visit::walk_argument(self, context, argument);
+    }
+
+    fn visit_exit_code(&mut self, context: &Context, exit_code: &'a OpenCliExitCode) {
+        self.record(context, &exit_code.code.to_string());
+    }
+
+    fn visit_metadata(&mut self, context: &Context, metadata: &'a OpenCliMetadata) {
+        self.record(context, &metadata.name);
+    }
+}
+
+const EXPECTED: [&str; 12] = [
+    "options[0] [] --color",
+    "options[0].arguments[0] [] when",
+    "options[0].arguments[0].metadata[0] [] format",
+    "arguments[0] [] file",
+    "commands[0] [remote] remote",
+    "commands[0].options[0] [remote] --config",
+    "commands[0].commands[0] [remote add] add",
+    "commands[0].commands[0].arguments[0] [remote add] url",
+    "commands[0].commands[0].exitCodes[0] [remote add] 3",
+    "commands[1] [build] build",
+    "exitCodes[0] [] 0",
+    "metadata[0] [] language",
+];
+
+#[test]
+fn visit_order_and_context() {
+    let mut recorder = Recorder::default();
+    recorder.visit_document(&document());
+    assert_eq!(recorder.visited, EXPECTED);
+}
+
+#[test]
+fn visit_mut_order_and_context() {
+    #[derive(Default)]
+    struct RecorderMut(Recorder);
+
+    impl VisitMut for RecorderMut {
+        fn visit_command_mut(&mut self, context: &Context, command: &mut OpenCliCommand) {
+            self.0.record(context, &command.name);
+            visit::walk_command_mut(self, context, command);
+        }
+
+        fn visit_option_mut(&mut self, context: &Context, option: &mut OpenCliOption) {
+            self.0.record(context, &option.name);
+            visit::walk_option_mut(self, context, option);
+        }
+
+        fn visit_argument_mut(&mut self, context: &Context, argument: &mut OpenCliArgument) {
+            self.0.record(context, &argument.name);
+            visit::walk_argument_mut(self, context, argument);
+        }
+
+        fn visit_exit_code_mut(&mut self, context: &Context, exit_code: &mut OpenCliExitCode) {
+            self.0.record(context, &exit_code.code.to_string());
+        }
+
+        fn visit_metadata_mut(&mut self, context: &Context, metadata: &mut OpenCliMetadata) {
+            self.0.record(context, &metadata.name);
+        }
+    }
+
+    let mut recorder = RecorderMut::default();
+    recorder.visit_document_mut(&mut document());
+    assert_eq!(recorder.0.visited, EXPECTED);
+}
+
+#[test]
+fn not_walking_skips_children() {
+    struct Commands(Vec<String>);
+
+    impl<'a> Visit<'a> for Commands {
+        fn visit_command(&mut self, _context: &Context, command: &'a OpenCliCommand) {
+            self.0.push(command.name.clone());
+        }
+    }
+
+    let mut commands = Commands(Vec::new());
+    commands.visit_document(&document());
+    assert_eq!(commands.0, ["remote", "build"]);
+}
+
+#[test]
+fn visit_mut_modifies() {
+    struct Rename;
+
+    impl VisitMut for Rename {
+        fn visit_option_mut(&mut self, context: &Context, option: &mut OpenCliOption) {
+            option.name = option.name.to_uppercase();
+            visit::walk_option_mut(self, context, option);
+        }
+    }
+
+    let mut document = document();
+    Rename.visit_document_mut(&mut document);
+    assert_eq!(document.options[0].name, "--COLOR");
+    assert_eq!(document.commands[0].options[0].name, "--CONFIG");
+}